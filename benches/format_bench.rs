@@ -3,7 +3,8 @@ use sqlfmt::{format_string, Mode};
 
 fn load_test_file(name: &str) -> String {
     let path = format!("tests/data/unformatted/{}", name);
-    let content = std::fs::read_to_string(&path).expect(&format!("Failed to read {}", path));
+    let content =
+        std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("Failed to read {}", path));
     // Golden test files use a sentinel to separate input/expected; take only input
     if let Some(pos) = content.find(")))))__SQLFMT_OUTPUT__(((((") {
         content[..pos].to_string()
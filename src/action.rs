@@ -27,6 +27,10 @@ pub enum Action {
     /// Handle semicolon: reset rule stack and flush.
     HandleSemicolon,
 
+    /// Handle a MySQL `DELIMITER` line: it ends the statement before it and
+    /// sets the terminator of the statements after it.
+    HandleDelimiter,
+
     /// Handle number: disambiguate unary operator prefix.
     HandleNumber,
 
@@ -36,9 +40,39 @@ pub enum Action {
     /// Non-reserved top-level keyword: check bracket depth.
     HandleNonreservedTopLevelKeyword { inner: &'static Action },
 
+    /// Keyword whose role depends on bracket depth: `top_level` outside of
+    /// brackets, `nested` inside them.
+    HandleTopLevelOrNestedKeyword {
+        top_level: &'static Action,
+        nested: &'static Action,
+    },
+
+    /// Keyword whose role depends on its position: `statement_start` when it
+    /// begins a statement (at the top of the file, after `;`, or at the start
+    /// of a procedural block section), `otherwise` anywhere else.
+    HandleStatementStartKeyword {
+        statement_start: &'static Action,
+        otherwise: &'static Action,
+    },
+
+    /// Keyword that is a clause only inside the brackets of MATCH_RECOGNIZE
+    /// (`measures`, `pattern`, `define`, ...): `inner` there, a name anywhere
+    /// else.
+    HandleMatchRecognizeKeyword { inner: &'static Action },
+
+    /// Handle a procedural block start (`begin`, `if`, `loop`, ...).
+    HandleBlockStart,
+
+    /// Handle a procedural block end (`end`, `end if`, `end loop`, ...).
+    HandleBlockEnd,
+
     /// Handle SET operators (UNION, INTERSECT, EXCEPT, MINUS).
     HandleSetOperator,
 
+    /// Handle the Snowflake flow operator (`->>`), which ends one statement
+    /// and pipes its result into the next.
+    HandleFlowOperator,
+
     /// Handle DDL AS keyword.
     HandleDdlAs,
 
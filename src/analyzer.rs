@@ -3,14 +3,24 @@ use memchr::memchr;
 use crate::action::Action;
 use crate::comment::Comment;
 use crate::error::SqlfmtError;
-use crate::lexer::{self, scan_dollar_string, LexState};
+use crate::lexer::{self, scan_dollar_string, LexState, Syntax};
 use crate::line::Line;
 use crate::node::{Node, NodeIndex};
-use crate::node_manager::NodeManager;
+use crate::node_manager::{ClauseNesting, NodeManager};
 use crate::query::Query;
 use crate::string_utils::skip_string_literal;
 use crate::token::{Token, TokenType};
 
+/// Where lexing stands relative to a T-SQL `IF`, `ELSE` or `WHILE` condition
+/// that is not followed by a `BEGIN ... END` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConditionBody {
+    /// After the condition: the next statement is its body.
+    Pending,
+    /// Inside the body, whose statement starts with this keyword.
+    Open(NodeIndex),
+}
+
 /// The byte-dispatch lexer. Parses SQL source strings into Queries.
 /// Maintains buffers and a lex state stack for nested lexing contexts.
 pub struct Analyzer {
@@ -18,6 +28,8 @@ pub struct Analyzer {
     pub node_manager: NodeManager,
     pub arena: Vec<Node>,
 
+    /// The dialect's keywords and tokens, consulted by the lexer.
+    syntax: Syntax,
     lex_state: Vec<LexState>,
     node_buffer: Vec<NodeIndex>,
     comment_buffer: Vec<Comment>,
@@ -35,13 +47,19 @@ pub struct Analyzer {
     /// Stored in the newline node's token prefix so formatting-disabled
     /// lines can preserve trailing whitespace before newlines.
     trailing_whitespace: String,
+    /// The statement terminator set by a MySQL `DELIMITER` line, if it is
+    /// not `;`.
+    delimiter: Option<String>,
+    /// The T-SQL condition whose single-statement body is being lexed.
+    condition_body: Option<ConditionBody>,
 }
 
 impl Analyzer {
-    pub fn new(node_manager: NodeManager, line_length: usize) -> Self {
+    pub fn new(node_manager: NodeManager, syntax: Syntax, line_length: usize) -> Self {
         Self {
             line_length,
             node_manager,
+            syntax,
             lex_state: vec![LexState::Main],
             node_buffer: Vec::new(),
             comment_buffer: Vec::new(),
@@ -51,6 +69,8 @@ impl Analyzer {
             suppress_next_newline: false,
             had_suppressed_newline: false,
             trailing_whitespace: String::new(),
+            delimiter: None,
+            condition_body: None,
         }
     }
 
@@ -91,14 +111,66 @@ impl Analyzer {
             .last()
             .expect("lex_state initialized with Main in Analyzer::new");
 
-        match lexer::lex_one(remaining, state) {
-            Some(result) => self.execute_action(
-                result.action,
-                result.match_len,
-                result.prefix,
-                result.token_text,
-                source,
-            ),
+        // A custom DELIMITER ends statements like a semicolon
+        if let Some(delimiter) = self
+            .delimiter
+            .as_deref()
+            .filter(|_| state != LexState::FmtOff)
+        {
+            let prefix_len = remaining.len() - remaining.trim_start_matches([' ', '\t']).len();
+            if remaining[prefix_len..].starts_with(delimiter) {
+                let match_len = prefix_len + delimiter.len();
+                return self.execute_action(
+                    &Action::HandleSemicolon,
+                    match_len,
+                    &remaining[..prefix_len],
+                    &remaining[prefix_len..match_len],
+                    source,
+                );
+            }
+        }
+
+        match lexer::lex_one(remaining, state, &self.syntax) {
+            Some(result) if self.is_hash_comment(source, result.prefix, result.token_text) => {
+                Err(SqlfmtError::Parsing {
+                    position: self.pos + result.prefix.len(),
+                    message: "`#` comments are only supported by the mysql and bigquery \
+                        dialects; use `--` or choose one of those dialects"
+                        .to_string(),
+                })
+            }
+            // ...so a `;` inside it, as in a procedure body, is part of the
+            // statement
+            Some(result)
+                if self.delimiter.is_some() && matches!(result.action, Action::HandleSemicolon) =>
+            {
+                self.add_node(result.prefix, result.token_text, TokenType::Data);
+                self.pos += result.match_len;
+                Ok(())
+            }
+            Some(result) => {
+                // ...and no token but a string or comment runs past it, as
+                // in `now()//` or `end$$`
+                let cut = self
+                    .delimiter
+                    .as_deref()
+                    .filter(|_| {
+                        result.match_len == result.prefix.len() + result.token_text.len()
+                            && !result
+                                .token_text
+                                .starts_with(['\'', '"', '`', '-', '#', '/'])
+                    })
+                    .and_then(|d| result.token_text.find(d))
+                    .filter(|&i| i > 0);
+                let token_text = cut.map_or(result.token_text, |i| &result.token_text[..i]);
+                self.execute_action(
+                    result.action,
+                    cut.map_or(result.match_len, |i| result.prefix.len() + i),
+                    result.prefix,
+                    token_text,
+                    source,
+                )
+            }
             None => Err(SqlfmtError::Parsing {
                 position: self.pos,
                 message: format!(
@@ -109,6 +181,22 @@ impl Analyzer {
         }
     }
 
+    /// Whether a lone `#` starts its line, as in a `# comment` in a dialect
+    /// without them. Lexing it as an operator would reflow the comment into
+    /// code.
+    fn is_hash_comment(&self, source: &str, prefix: &str, token_text: &str) -> bool {
+        if token_text != "#" || self.syntax.hash_comments {
+            return false;
+        }
+        let start = self.pos + prefix.len();
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        source[line_start..start].trim().is_empty()
+            && source[start + 1..]
+                .chars()
+                .next()
+                .is_none_or(char::is_whitespace)
+    }
+
     /// Dispatch an action based on what the rule matched.
     fn execute_action(
         &mut self,
@@ -166,14 +254,31 @@ impl Analyzer {
             Action::HandleSemicolon => {
                 self.add_node(prefix, token_text, TokenType::Semicolon);
                 self.flush_line_buffer();
-                while self.lex_state.len() > 1 {
-                    self.lex_state.pop();
-                }
+                self.pop_statement_states();
                 self.node_manager.reset();
                 self.suppress_next_newline = true;
                 self.pos += match_len;
             }
 
+            Action::HandleDelimiter => {
+                if !self.at_statement_start() {
+                    // Only the word: a column named `delimiter`
+                    let word_len = "delimiter".len();
+                    self.add_node(prefix, &token_text[..word_len], TokenType::Name);
+                    self.pos += prefix.len() + word_len;
+                    return Ok(());
+                }
+                let terminator = token_text.split_whitespace().last().unwrap_or(";");
+                self.delimiter = (terminator != ";").then(|| terminator.to_string());
+                self.execute_action(
+                    &Action::HandleSemicolon,
+                    match_len,
+                    prefix,
+                    token_text,
+                    _source,
+                )?;
+            }
+
             Action::HandleNumber => {
                 self.add_node(prefix, token_text, TokenType::Number);
                 self.pos += match_len;
@@ -183,7 +288,9 @@ impl Analyzer {
                 let prev_is_dot = self
                     .previous_node_index()
                     .is_some_and(|_idx| self.get_prev_sql_type() == Some(TokenType::Dot));
-                if prev_is_dot {
+                // A keyword before a paren (`safe.replace(`) is handled by its
+                // inner action, which leaves the paren to be lexed on its own
+                if prev_is_dot && !matches!(inner, Action::HandleKeywordBeforeParen { .. }) {
                     self.add_node(prefix, token_text, TokenType::Name);
                     self.pos += match_len;
                 } else {
@@ -200,6 +307,83 @@ impl Analyzer {
                 }
             }
 
+            Action::HandleTopLevelOrNestedKeyword { top_level, nested } => {
+                let inner = if self.node_manager.open_brackets.is_empty() {
+                    top_level
+                } else {
+                    nested
+                };
+                self.execute_action(inner, match_len, prefix, token_text, _source)?;
+            }
+
+            Action::HandleStatementStartKeyword {
+                statement_start,
+                otherwise,
+            } => {
+                let inner = if self.at_statement_start() {
+                    statement_start
+                } else {
+                    otherwise
+                };
+                self.execute_action(inner, match_len, prefix, token_text, _source)?;
+            }
+
+            Action::HandleMatchRecognizeKeyword { inner } => {
+                if !self.in_match_recognize() {
+                    // Only the first word: a column named `measures` or `define`
+                    let word_len = token_text.split_whitespace().next().map_or(0, str::len);
+                    self.add_node(prefix, &token_text[..word_len], TokenType::Name);
+                    self.pos += prefix.len() + word_len;
+                    return Ok(());
+                }
+                self.execute_action(inner, match_len, prefix, token_text, _source)?;
+                if token_text.eq_ignore_ascii_case("pattern") {
+                    self.add_row_pattern(_source);
+                }
+            }
+
+            Action::HandleBlockStart => {
+                // A BEGIN that follows a DECLARE section replaces it rather
+                // than nesting inside it.
+                let after_declare =
+                    token_text.eq_ignore_ascii_case("begin")
+                        && self.node_manager.open_blocks.last().is_some_and(|&idx| {
+                            self.arena[idx].value.eq_ignore_ascii_case("declare")
+                        });
+                let token_type = if after_declare {
+                    TokenType::BlockKeyword
+                } else {
+                    TokenType::BlockStart
+                };
+                self.add_node(prefix, token_text, token_type);
+                self.pos += match_len;
+            }
+
+            Action::HandleBlockEnd => {
+                // END also ends a statement that has no semicolon (T-SQL)
+                self.pop_statement_states();
+                // T-SQL COMMIT / ROLLBACK only close a BEGIN TRAN block
+                let first_word = token_text.split_whitespace().next().unwrap_or("");
+                let ends_transaction = first_word.eq_ignore_ascii_case("commit")
+                    || first_word.eq_ignore_ascii_case("rollback");
+                let token_type = match self.node_manager.open_blocks.last() {
+                    Some(&idx)
+                        if !ends_transaction
+                            || (self.arena[idx]
+                                .value
+                                .get(..10)
+                                .is_some_and(|v| v.eq_ignore_ascii_case("begin tran"))
+                                && !self.follows_condition()) =>
+                    {
+                        TokenType::BlockEnd
+                    }
+                    _ if ends_transaction => TokenType::UntermKeyword,
+                    _ => TokenType::Name,
+                };
+                self.add_node(prefix, token_text, token_type);
+                self.pos += match_len;
+            }
+
             Action::HandleSetOperator => {
                 // Only flush if there's buffered content; don't create a spurious
                 // blank line when the previous newline already flushed the buffer.
@@ -213,6 +397,19 @@ impl Analyzer {
                 self.pos += match_len;
             }
 
+            Action::HandleFlowOperator => {
+                // Formatted like a set operator, but it also ends the
+                // statement before it.
+                self.pop_statement_states();
+                self.execute_action(
+                    &Action::HandleSetOperator,
+                    match_len,
+                    prefix,
+                    token_text,
+                    _source,
+                )?;
+            }
+
             Action::HandleDdlAs => {
                 self.add_node(prefix, token_text, TokenType::UntermKeyword);
                 self.pos += match_len;
@@ -263,9 +460,11 @@ impl Analyzer {
                 let keyword = token_text.trim_end_matches('(').trim_end();
 
                 // For star modifiers (except/exclude/replace): only use WordOperator
-                // when preceded by Star. Otherwise it's a function call → Name.
+                // when preceded by Star or by another star modifier's list.
+                // Otherwise it's a function call → Name.
                 let effective_type = if *token_type == TokenType::WordOperator
                     && self.get_prev_sql_type() != Some(TokenType::Star)
+                    && !self.follows_star_modifier()
                 {
                     TokenType::Name
                 } else {
@@ -284,6 +483,12 @@ impl Analyzer {
                     "function" => LexState::Function,
                     "warehouse" => LexState::Warehouse,
                     "clone" => LexState::Clone,
+                    "insert" => LexState::Insert,
+                    "update" => LexState::Update,
+                    "merge" => LexState::Merge,
+                    "create_table" => LexState::CreateTable,
+                    "block" => LexState::Block,
+                    "stage" => LexState::Stage,
                     _ => LexState::Unsupported,
                 };
                 self.push_state(new_state);
@@ -364,7 +569,10 @@ impl Analyzer {
         let remaining = &source[self.pos..];
         let mut check = remaining.trim_start();
         loop {
-            if check.starts_with("--") || check.starts_with("//") || check.starts_with('#') {
+            if check.starts_with("--")
+                || check.starts_with("//")
+                || (self.syntax.hash_comments && check.starts_with('#'))
+            {
                 if let Some(nl) = check.find('\n') {
                     check = check[nl + 1..].trim_start();
                 } else {
@@ -383,7 +591,7 @@ impl Analyzer {
         let next_is_quoted = check.starts_with('\'')
             || check.starts_with('"')
             || check.starts_with('`')
-            || check.starts_with("$$");
+            || (check.starts_with('$') && crate::lexer::scan_dollar_string(check.as_bytes()) > 0);
         if !next_is_quoted {
             self.pop_state();
         }
@@ -472,8 +680,19 @@ impl Analyzer {
         let epos = spos + (prefix.len() + token_text.len()) as u32;
         let token = Token::new(token_type, prefix, token_text, spos, epos);
 
+        let starts_condition_body =
+            self.syntax.tsql_blocks && self.track_condition_body(token_type, token_text);
         let prev = self.previous_node_index();
-        let node = self.node_manager.create_node(token, prev, &self.arena);
+        let mut node = self.node_manager.create_node(token, prev, &self.arena);
+        node.starts_condition_body = starts_condition_body;
+        if token_type == TokenType::BracketOpen && node.value == "(" && node.prefix.is_empty() {
+            if let Some(p) = prev.filter(|&p| {
+                self.arena[p].token.token_type == TokenType::Name
+                    && !self.arena[p].formatting_disabled
+            }) {
+                self.node_manager.case_function_name(&mut self.arena[p]);
+            }
+        }
         let idx = self.arena.len();
         self.arena.push(node);
 
@@ -653,6 +872,15 @@ impl Analyzer {
         }
     }
 
+    /// True if the next token begins a statement: nothing precedes it, or the
+    /// previous SQL token is a `;` or opens a procedural block section.
+    fn at_statement_start(&self) -> bool {
+        match self.get_prev_sql_type() {
+            None => true,
+            Some(tt) => tt == TokenType::Semicolon || tt.is_opening_block(),
+        }
+    }
+
     /// Get the token type of the previous meaningful SQL token.
     fn get_prev_sql_type(&self) -> Option<TokenType> {
         let mut idx = self.previous_node_index();
@@ -666,6 +894,180 @@ impl Analyzer {
         None
     }
 
+    /// Whether the innermost open bracket is the one after MATCH_RECOGNIZE.
+    fn in_match_recognize(&self) -> bool {
+        let Some(&bracket) = self.node_manager.open_brackets.last() else {
+            return false;
+        };
+        let mut idx = self.arena[bracket].previous_node;
+        while let Some(i) = idx {
+            let node = &self.arena[i];
+            if !node.token.token_type.does_not_set_prev_sql_context() {
+                return node.value.eq_ignore_ascii_case("match_recognize");
+            }
+            idx = node.previous_node;
+        }
+        false
+    }
+
+    /// Add the row pattern after PATTERN, `(down+ up+)`, as a single token,
+    /// so its quantifiers are kept as written.
+    fn add_row_pattern(&mut self, source: &str) {
+        let rest = &source[self.pos..];
+        let after_prefix = rest.trim_start_matches([' ', '\t']);
+        let prefix_len = rest.len() - after_prefix.len();
+        if !after_prefix.starts_with('(') {
+            return;
+        }
+        let mut depth = 0;
+        for (i, b) in after_prefix.bytes().enumerate() {
+            match b {
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ => continue,
+            }
+            if depth == 0 {
+                let (prefix, text) = rest.split_at(prefix_len);
+                self.add_node(prefix, &text[..=i], TokenType::QuotedName);
+                self.pos += prefix_len + i + 1;
+                return;
+            }
+        }
+    }
+
+    /// Indent the statement after a T-SQL `IF`, `ELSE` or `WHILE` condition
+    /// under it, and close it at the next statement: `if @@error <> 0`
+    /// then `rollback tran` one level deeper. Returns whether this token
+    /// starts the body.
+    fn track_condition_body(&mut self, token_type: TokenType, token_text: &str) -> bool {
+        match token_type {
+            TokenType::Semicolon
+            | TokenType::BlockStart
+            | TokenType::BlockKeyword
+            | TokenType::BlockEnd => {
+                self.condition_body = None;
+                return false;
+            }
+            TokenType::UntermKeyword if self.node_manager.open_brackets.is_empty() => {}
+            // PRINT, EXEC, RETURN, ... are names, but still start the body
+            TokenType::Name
+                if self.node_manager.open_brackets.is_empty()
+                    && self.condition_body == Some(ConditionBody::Pending)
+                    && is_tsql_statement_name(token_text) =>
+            {
+                self.condition_body = Some(ConditionBody::Open(self.arena.len()));
+                return true;
+            }
+            _ => return false,
+        }
+        let keyword = token_text
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let nesting = match self.condition_body {
+            Some(ConditionBody::Pending) => ClauseNesting::Nest,
+            Some(ConditionBody::Open(first)) if self.starts_statement(&keyword, first) => {
+                ClauseNesting::CloseAll
+            }
+            // A clause of the body's statement, which stays in the body
+            // after a set operator
+            Some(ConditionBody::Open(_)) => {
+                if self.get_prev_sql_type() == Some(TokenType::SetOperator) {
+                    self.node_manager.next_clause = ClauseNesting::Nest;
+                }
+                return false;
+            }
+            None => ClauseNesting::Replace,
+        };
+        self.node_manager.next_clause = nesting;
+        let starts_body = nesting == ClauseNesting::Nest;
+        self.condition_body = if matches!(keyword.as_str(), "if" | "else" | "while") {
+            Some(ConditionBody::Pending)
+        } else if starts_body {
+            Some(ConditionBody::Open(self.arena.len()))
+        } else {
+            None
+        };
+        starts_body
+    }
+
+    /// Whether `keyword` starts a new T-SQL statement, rather than a clause
+    /// of the statement that starts with the `first` keyword.
+    fn starts_statement(&self, keyword: &str, first_idx: NodeIndex) -> bool {
+        let first = self.arena[first_idx]
+            .value
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match keyword {
+            "select" => {
+                !matches!(first.as_str(), "insert" | "with")
+                    && self.get_prev_sql_type() != Some(TokenType::SetOperator)
+            }
+            // The first SET after UPDATE is its clause
+            "set" => {
+                first != "update"
+                    || self.arena[first_idx..]
+                        .iter()
+                        .any(|n| n.is_unterm_keyword() && n.value.eq_ignore_ascii_case("set"))
+            }
+            "insert" | "update" | "delete" | "merge" | "declare" | "truncate" | "if" | "else"
+            | "while" | "commit" | "rollback" => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the current statement is the body of a T-SQL `IF`, `ELSE` or
+    /// `WHILE`, as in `if @@error <> 0 rollback`.
+    fn follows_condition(&self) -> bool {
+        let mut idx = self.previous_node_index();
+        while let Some(i) = idx {
+            let node = &self.arena[i];
+            match node.token.token_type {
+                TokenType::UntermKeyword => {
+                    return ["if", "else", "while"]
+                        .iter()
+                        .any(|kw| node.value.eq_ignore_ascii_case(kw));
+                }
+                TokenType::Semicolon | TokenType::BlockStart | TokenType::BlockEnd => {
+                    return false;
+                }
+                _ => idx = node.previous_node,
+            }
+        }
+        false
+    }
+
+    /// Whether the previous SQL token closes a star modifier's list, as in
+    /// `* except (a) replace (b as c)`.
+    fn follows_star_modifier(&self) -> bool {
+        let mut depth = 0usize;
+        let mut idx = self.previous_node_index();
+        while let Some(i) = idx {
+            let node = &self.arena[i];
+            match node.token.token_type {
+                TokenType::BracketClose => depth += 1,
+                TokenType::BracketOpen if depth == 1 => {
+                    return node.previous_node.is_some_and(|p| {
+                        let prev = &self.arena[p];
+                        prev.token.token_type == TokenType::WordOperator
+                            && ["except", "exclude", "replace"]
+                                .iter()
+                                .any(|kw| prev.value.eq_ignore_ascii_case(kw))
+                    });
+                }
+                TokenType::BracketOpen if depth > 1 => depth -= 1,
+                TokenType::BracketOpen => return false,
+                tt if depth == 0 && !tt.does_not_set_prev_sql_context() => return false,
+                _ => {}
+            }
+            idx = node.previous_node;
+        }
+        false
+    }
+
     /// Clear all buffers for a fresh parse.
     fn clear_buffers(&mut self) {
         self.node_buffer.clear();
@@ -676,6 +1078,8 @@ impl Analyzer {
         self.suppress_next_newline = false;
         self.had_suppressed_newline = false;
         self.trailing_whitespace.clear();
+        self.delimiter = None;
+        self.condition_body = None;
         self.node_manager.reset();
     }
 
@@ -876,6 +1280,36 @@ impl Analyzer {
             self.lex_state.pop();
         }
     }
+
+    /// Leave the lex states of a finished statement. Inside a procedural
+    /// block, pop back to the block's state rather than all the way to Main.
+    fn pop_statement_states(&mut self) {
+        let keep_block = !self.node_manager.open_blocks.is_empty();
+        while self.lex_state.len() > 1
+            && !(keep_block && self.lex_state.last() == Some(&LexState::Block))
+        {
+            self.lex_state.pop();
+        }
+    }
+}
+
+/// T-SQL statements that start with a word lexed as a name.
+fn is_tsql_statement_name(word: &str) -> bool {
+    [
+        "print",
+        "set",
+        "exec",
+        "execute",
+        "return",
+        "break",
+        "continue",
+        "raiserror",
+        "throw",
+        "goto",
+        "waitfor",
+    ]
+    .iter()
+    .any(|kw| word.eq_ignore_ascii_case(kw))
 }
 
 /// Skip a Jinja block ({{ }}, {% %}, or {# #}) starting at position `i`.
@@ -914,7 +1348,7 @@ mod tests {
 
     fn create_analyzer() -> Analyzer {
         let nm = NodeManager::new(false);
-        Analyzer::new(nm, 88)
+        Analyzer::new(nm, Syntax::new(lexer::ALL_DIALECT_KEYWORDS.clone()), 88)
     }
 
     #[test]
@@ -2,10 +2,12 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use crate::error::SqlfmtError;
+use crate::explicit_aliases;
 use crate::formatter::QueryFormatter;
 use crate::mode::Mode;
+use crate::redundant_parens;
 use crate::report::{FileResult, Report};
-use crate::string_utils::skip_string_literal_into;
+use crate::string_utils::{skip_string_literal_into, split_dollar_quoted, to_single_quoted};
 
 /// Lightweight snapshot of a token for safety-check comparison.
 /// Avoids re-lexing the original source by capturing token_type + text
@@ -21,29 +23,67 @@ struct TokenSnapshot {
 pub fn format_string(source: &str, mode: &Mode) -> Result<String, SqlfmtError> {
     let dialect = mode.dialect()?;
 
-    let mut analyzer = dialect.initialize_analyzer(mode.line_length);
+    let new_analyzer = || {
+        let mut analyzer = dialect.initialize_analyzer(mode.line_length);
+        let nm = &mut analyzer.node_manager;
+        nm.keyword_case = mode.keyword_case;
+        if let Some(case) = mode.identifier_case {
+            nm.identifier_case = case;
+        }
+        nm.function_case = mode.function_case.unwrap_or(nm.identifier_case);
+        nm.operators = mode.normalize_operators;
+        nm.single_quote_strings =
+            mode.single_quote_strings && dialect.syntax().double_quoted_strings;
+        analyzer
+    };
+    let mut analyzer = new_analyzer();
     let mut query = analyzer.parse_query(source)?;
     let mut arena = std::mem::take(&mut analyzer.arena);
 
+    // The pairs are cut from the source, which is parsed again. The new
+    // tokens must be the old ones minus exactly those brackets.
+    if mode.remove_redundant_parens {
+        let removed = redundant_parens::redundant_parens(&query, &arena);
+        if !removed.is_empty() {
+            let stripped = redundant_parens::strip_nodes(source, &arena, &removed);
+            let expected = mode.should_safety_check().then(|| {
+                snapshot_nodes(
+                    query
+                        .lines
+                        .iter()
+                        .flat_map(|l| l.nodes.iter())
+                        .filter(|i| !removed.contains(i))
+                        .map(|&i| &arena[i]),
+                )
+            });
+            let mut analyzer = new_analyzer();
+            query = analyzer.parse_query(&stripped)?;
+            arena = std::mem::take(&mut analyzer.arena);
+            if let Some(expected) = expected {
+                let actual = snapshot_nodes(query.tokens(&arena).into_iter());
+                compare_token_snapshots(&expected, &actual, mode)?;
+            }
+        }
+    }
+    query.indent = mode.indent();
+
     // Capture token snapshot before formatting (for safety check reuse).
     // This avoids re-lexing the original source in safety_check.
     let original_tokens = if mode.should_safety_check() {
-        Some(
-            query
-                .tokens(&arena)
-                .into_iter()
-                .filter(|n| n.token.token_type != crate::token::TokenType::Newline)
-                .map(|n| TokenSnapshot {
-                    token_type: n.token.token_type,
-                    text: n.token.text.clone(),
-                })
-                .collect::<Vec<_>>(),
-        )
+        Some(snapshot_nodes(query.tokens(&arena).into_iter()))
     } else {
         None
     };
 
-    let formatter = QueryFormatter::new(mode.line_length, mode.no_jinjafmt);
+    // Inserted after the snapshot, so the safety check allows the added
+    // `as` tokens and nothing else.
+    if mode.explicit_aliases {
+        explicit_aliases::insert_explicit_aliases(&mut query, &mut arena, mode.keyword_case);
+    }
+
+    format_sql_bodies(&query, &mut arena, mode);
+
+    let formatter = QueryFormatter::new(mode);
     formatter.format(&mut query, &mut arena);
 
     let result = query.render(&arena);
@@ -63,6 +103,119 @@ pub fn format_string(source: &str, mode: &Mode) -> Result<String, SqlfmtError> {
     Ok(result)
 }
 
+/// Format the dollar-quoted bodies of CREATE FUNCTION / CREATE PROCEDURE
+/// statements that declare `language sql`, and of EXECUTE IMMEDIATE blocks,
+/// by running the full pipeline on each body and splicing the result back,
+/// indented one level past the keyword that introduces it. Bodies that fail
+/// to format are left as-is.
+fn format_sql_bodies(query: &crate::query::Query, arena: &mut [crate::node::Node], mode: &Mode) {
+    use crate::token::TokenType;
+
+    let mut is_routine = false;
+    let mut is_sql = false;
+    let mut prev: Option<usize> = None;
+    // (body node, indent level of the `as` keyword before it)
+    let mut bodies: Vec<(usize, usize)> = Vec::new();
+
+    let indices: Vec<usize> = query
+        .lines
+        .iter()
+        .flat_map(|l| l.nodes.iter().copied())
+        .collect();
+    for (i, &idx) in indices.iter().enumerate() {
+        let node = &arena[idx];
+        let tt = node.token.token_type;
+        if tt == TokenType::Newline {
+            continue;
+        }
+        match tt {
+            TokenType::UntermKeyword => {
+                let kw = node.value.to_ascii_lowercase();
+                if kw.starts_with("create ")
+                    && (kw.contains("function") || kw.contains("procedure"))
+                {
+                    is_routine = true;
+                }
+            }
+            TokenType::Name => {
+                let prev_node = prev.map(|p| &arena[p]);
+                let after_kw = |kw: &str| {
+                    prev_node.is_some_and(|p| {
+                        p.token.token_type == TokenType::UntermKeyword
+                            && p.value.eq_ignore_ascii_case(kw)
+                    })
+                };
+                if after_kw("language") && node.value.eq_ignore_ascii_case("sql") {
+                    is_sql = true;
+                }
+                let is_script = after_kw("execute immediate");
+                if (after_kw("as") || is_script)
+                    && !node.formatting_disabled
+                    && split_dollar_quoted(&node.token.text).is_some()
+                {
+                    let (sql, jinja) = prev_node.map(|p| p.depth()).unwrap_or((0, 0));
+                    bodies.push((idx, sql + jinja));
+                    if is_script {
+                        // Snowflake Scripting blocks are always SQL
+                        is_routine = true;
+                        is_sql = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+        prev = Some(idx);
+
+        let at_end = i + 1 == indices.len();
+        if tt == TokenType::Semicolon || at_end {
+            if is_routine && is_sql {
+                for &(body_idx, level) in &bodies {
+                    if let Some(value) = format_sql_body(&arena[body_idx].token.text, level, mode) {
+                        arena[body_idx].value = compact_str::CompactString::from(value);
+                    }
+                }
+            }
+            is_routine = false;
+            is_sql = false;
+            bodies.clear();
+        }
+    }
+}
+
+/// Format one dollar-quoted SQL body. The body lines are indented one level
+/// past `level` and the closing tag sits at `level`. Returns None if the
+/// body is empty, fails to format, or would not survive re-indentation
+/// unchanged (e.g. it contains multiline string literals).
+fn format_sql_body(text: &str, level: usize, mode: &Mode) -> Option<String> {
+    let (tag, body) = split_dollar_quoted(text)?;
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+    let indent = mode.indent();
+    let inner_mode = Mode {
+        line_length: mode.line_length.saturating_sub(indent.columns(level + 1)),
+        // the body must lex to the same tokens as the original
+        explicit_aliases: false,
+        remove_redundant_parens: false,
+        ..mode.clone()
+    };
+    let formatted = format_string(body, &inner_mode).ok()?;
+
+    let mut inner = String::with_capacity(formatted.len());
+    for line in formatted.lines() {
+        if !line.is_empty() {
+            inner.push_str(indent.prefix(level + 1));
+            inner.push_str(line);
+        }
+        inner.push('\n');
+    }
+    if !dollar_bodies_equivalent(body, &inner, mode) {
+        return None;
+    }
+    Some(format!("{tag}\n{inner}{}{tag}", indent.prefix(level)))
+}
+
 /// Run the formatter on a collection of files.
 pub async fn run(files: &[PathBuf], mode: &Mode) -> Report {
     let matching_paths = get_matching_paths(files, mode);
@@ -316,18 +469,74 @@ fn safety_check(
     formatted: &str,
     mode: &Mode,
 ) -> Result<(), SqlfmtError> {
-    use crate::token::TokenType;
+    let mut tokens2 = token_snapshots(formatted, mode)?;
+    if mode.explicit_aliases {
+        tokens2 = without_inserted_aliases(original_tokens, tokens2);
+    }
+    compare_token_snapshots(original_tokens, &tokens2, mode)
+}
+
+/// Drop the `as` tokens of `formatted` that the original does not have at
+/// the same position: the ones `explicit_aliases` inserted.
+fn without_inserted_aliases(
+    original: &[TokenSnapshot],
+    formatted: Vec<TokenSnapshot>,
+) -> Vec<TokenSnapshot> {
+    let is_as = |t: &TokenSnapshot| {
+        t.token_type == crate::token::TokenType::WordOperator && t.text.eq_ignore_ascii_case("as")
+    };
+    let mut original = original.iter().peekable();
+    formatted
+        .into_iter()
+        .filter(|t| {
+            if is_as(t) && !original.peek().is_some_and(|o| is_as(o)) {
+                return false;
+            }
+            original.next();
+            true
+        })
+        .collect()
+}
 
+/// Lex `source` and capture its non-newline tokens for comparison.
+fn token_snapshots(source: &str, mode: &Mode) -> Result<Vec<TokenSnapshot>, SqlfmtError> {
     let dialect = mode.dialect()?;
-    let mut analyzer2 = dialect.initialize_analyzer(mode.line_length);
-    let query2 = analyzer2.parse_query(formatted)?;
+    let mut analyzer = dialect.initialize_analyzer(mode.line_length);
+    let query = analyzer.parse_query(source)?;
 
-    let tokens2: Vec<_> = query2
-        .tokens(&analyzer2.arena)
-        .into_iter()
-        .filter(|n| n.token.token_type != TokenType::Newline)
-        .collect();
+    Ok(snapshot_nodes(query.tokens(&analyzer.arena).into_iter()))
+}
 
+/// Capture the non-newline tokens of `nodes` for comparison.
+fn snapshot_nodes<'a>(nodes: impl Iterator<Item = &'a crate::node::Node>) -> Vec<TokenSnapshot> {
+    nodes
+        .filter(|n| !n.is_newline())
+        .map(|n| TokenSnapshot {
+            token_type: n.token.token_type,
+            text: n.token.text.clone(),
+        })
+        .collect()
+}
+
+/// True if two dollar-quoted bodies lex to equivalent token streams.
+fn dollar_bodies_equivalent(body1: &str, body2: &str, mode: &Mode) -> bool {
+    match (
+        token_snapshots(body1.trim(), mode),
+        token_snapshots(body2.trim(), mode),
+    ) {
+        (Ok(t1), Ok(t2)) => compare_token_snapshots(&t1, &t2, mode).is_ok(),
+        _ => false,
+    }
+}
+
+/// Compare two token streams by type and normalized text.
+/// Dollar-quoted strings whose bodies were reformatted (SQL function bodies)
+/// are compared by their nested tokens rather than their raw text.
+fn compare_token_snapshots(
+    original_tokens: &[TokenSnapshot],
+    tokens2: &[TokenSnapshot],
+    mode: &Mode,
+) -> Result<(), SqlfmtError> {
     if original_tokens.len() != tokens2.len() {
         return Err(SqlfmtError::Equivalence(format!(
             "Token count mismatch: original has {} tokens, formatted has {}",
@@ -336,34 +545,41 @@ fn safety_check(
         )));
     }
 
-    for (i, (s1, n2)) in original_tokens.iter().zip(tokens2.iter()).enumerate() {
-        if s1.token_type != n2.token.token_type {
+    for (i, (s1, s2)) in original_tokens.iter().zip(tokens2.iter()).enumerate() {
+        if s1.token_type != s2.token_type {
             return Err(SqlfmtError::Equivalence(format!(
                 "Token type mismatch at position {}: original {:?} '{}', formatted {:?} '{}'",
-                i, s1.token_type, s1.text, n2.token.token_type, n2.token.text
+                i, s1.token_type, s1.text, s2.token_type, s2.text
             )));
         }
         // Fast path: if token text is identical, skip normalization entirely
-        if s1.text == n2.token.text {
+        if s1.text == s2.text {
             continue;
         }
         // Fast path: if case-insensitively equal and single-word non-Jinja, skip
         if !s1.token_type.is_jinja()
             && !s1.text.contains(char::is_whitespace)
-            && !n2.token.text.contains(char::is_whitespace)
-            && s1.text.eq_ignore_ascii_case(&n2.token.text)
+            && !s2.text.contains(char::is_whitespace)
+            && s1.text.eq_ignore_ascii_case(&s2.text)
         {
             continue;
         }
+        if let (Some((tag1, body1)), Some((tag2, body2))) =
+            (split_dollar_quoted(&s1.text), split_dollar_quoted(&s2.text))
+        {
+            if tag1 == tag2 && dollar_bodies_equivalent(body1, body2, mode) {
+                continue;
+            }
+        }
         // Slow path: full normalization needed
         let t1 = s1.text.to_lowercase();
-        let t2 = n2.token.text.to_lowercase();
+        let t2 = s2.text.to_lowercase();
         let t1_norm = normalize_token_text(&t1, s1.token_type);
-        let t2_norm = normalize_token_text(&t2, n2.token.token_type);
+        let t2_norm = normalize_token_text(&t2, s2.token_type);
         if t1_norm != t2_norm {
             return Err(SqlfmtError::Equivalence(format!(
                 "Token text mismatch at position {}: original '{}', formatted '{}'",
-                i, s1.text, n2.token.text
+                i, s1.text, s2.text
             )));
         }
     }
@@ -417,6 +633,16 @@ fn normalize_token_text(text: &str, token_type: crate::token::TokenType) -> Stri
             let normalized = normalize_jinja_structure(&normalized);
             format!("{{% {} %}}", normalized)
         }
+        // Spellings that normalize_operators may rewrite
+        TokenType::Operator => match text {
+            "!=" => "<>".to_string(),
+            "==" => "=".to_string(),
+            _ => text.to_string(),
+        },
+        // Column types, whose parameters are respaced
+        TokenType::DataType => text.split_whitespace().collect(),
+        // `"..."` strings that single_quote_strings may rewrite
+        TokenType::Name => to_single_quoted(text).unwrap_or_else(|| join_whitespace(text)),
         _ => join_whitespace(text),
     }
 }
@@ -579,6 +805,107 @@ mod tests {
         assert!(result.contains("t"));
     }
 
+    #[test]
+    fn test_format_explicit_aliases() {
+        let mode = Mode {
+            explicit_aliases: true,
+            ..Mode::default()
+        };
+        let format = |source: &str| format_string(source, &mode).unwrap();
+        assert_eq!(
+            format("select a b, count(*) \"N\" from t x join u y on x.id = y.id\n"),
+            "select\n    a as b\n    , count(*) as \"N\"\nfrom t as x\njoin u as y on x.id = y.id\n"
+        );
+        assert_eq!(
+            format("select a from t, lateral (select 1) z\n"),
+            "select a\nfrom\n    t\n    , lateral(select 1) as z\n"
+        );
+        for source in [
+            "select a as b, c from t as x\n",
+            "select date '2024-01-01', interval '1' day, x::double precision\n",
+            "select distinct on (a) a from t final\n",
+            "select {{ prefix }}_name from t\n",
+            "select a from t x (c, d) where b = c\n",
+            "select a collate \"C\" from t\n",
+            "select * from unnest(arr) with ordinality\n",
+        ] {
+            assert_eq!(
+                format(source),
+                format_string(source, &Mode::default()).unwrap()
+            );
+        }
+        let bigquery = Mode {
+            dialect_name: "bigquery".to_string(),
+            ..mode.clone()
+        };
+        assert_eq!(
+            format_string("select * from t for system_time as of ts\n", &bigquery).unwrap(),
+            "select * from t for system_time as of ts\n"
+        );
+    }
+
+    #[test]
+    fn test_format_single_quote_strings() {
+        let source = "select \"it's\", \"say \"\"hi\"\"\", 'a', \"\"\"b\"\"\" from t\n";
+        let mode = |dialect: &str| Mode {
+            dialect_name: dialect.to_string(),
+            single_quote_strings: true,
+            ..Mode::default()
+        };
+        assert_eq!(
+            format_string(source, &mode("bigquery")).unwrap(),
+            "select\n    'it\\'s'\n    , 'say \"hi\"'\n    , 'a'\n    , \"\"\"b\"\"\"\nfrom t\n"
+        );
+        // double quotes are identifiers here
+        let postgres = Mode {
+            single_quote_strings: false,
+            ..mode("postgres")
+        };
+        assert_eq!(
+            format_string(source, &mode("postgres")).unwrap(),
+            format_string(source, &postgres).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_remove_redundant_parens() {
+        let mode = Mode {
+            remove_redundant_parens: true,
+            ..Mode::default()
+        };
+        let format = |source: &str| format_string(source, &mode).unwrap();
+        assert_eq!(
+            format("select 1 where ((a = 1) and ((b = 2)))\n"),
+            "select 1 where a = 1 and b = 2\n"
+        );
+        assert_eq!(
+            format("select count((x)) from t join u on (t.id = u.id) where a * ((b + c)) > 0\n"),
+            "select count(x) from t join u on t.id = u.id where a * (b + c) > 0\n"
+        );
+        for source in [
+            "select a - (b - c), (a + 1)::int, -(-a), (t).f, (arr)[1]\n",
+            "select 1 where x in (1) and exists (select 1) and x between a and (b = c)\n",
+            "select 1 from t join u using (id) where not (a or b) and a = (b like c)\n",
+            "insert into t values (1), (2)\n",
+            "create function f() imports = ('@stage/f.py') as $$ 1 $$\n",
+            "select {{ a }} * ({{ b }}) from ((select 1)) as s\n",
+            "select (a || b) + c, a ^ (b * c), (a -> 'k') = b\n",
+            "select distinct on (a) a, b from t\n",
+        ] {
+            assert_eq!(
+                format(source),
+                format_string(source, &Mode::default()).unwrap()
+            );
+        }
+        // `||` is `or` in MySQL
+        let mysql = Mode {
+            dialect_name: "mysql".to_string(),
+            ..mode.clone()
+        };
+        let source = "select 1 where a and (b || c)\n";
+        assert_eq!(format_string(source, &mysql).unwrap(), source);
+    }
+
     #[test]
     fn test_format_empty_string() {
         let mode = Mode::default();
@@ -597,12 +924,53 @@ mod tests {
 
     #[test]
     fn test_format_with_duckdb_dialect() {
-        let mut mode = Mode::default();
-        mode.dialect_name = "duckdb".to_string();
+        let mode = Mode {
+            dialect_name: "duckdb".to_string(),
+            ..Mode::default()
+        };
         let result = format_string("SELECT 1\n", &mode).unwrap();
         assert!(result.contains("select"));
     }
 
+    #[test]
+    fn test_format_dialect_specific_keywords() {
+        let source =
+            "select customer_id, order_total from orders prewhere order_date >= today() - 30\n";
+        let clickhouse = Mode {
+            dialect_name: "clickhouse".to_string(),
+            ..Mode::default()
+        };
+        assert_eq!(
+            format_string(source, &clickhouse).unwrap(),
+            "select\n    customer_id\n    , order_total\nfrom orders\nprewhere order_date >= today() - 30\n"
+        );
+        let duckdb = Mode {
+            dialect_name: "duckdb".to_string(),
+            ..Mode::default()
+        };
+        assert_eq!(
+            format_string(source, &duckdb).unwrap(),
+            "select\n    customer_id\n    , order_total\nfrom orders prewhere order_date >= today() - 30\n"
+        );
+    }
+
+    #[test]
+    fn test_safety_check_compares_dollar_quoted_bodies() {
+        let mode = Mode::default();
+        let source = "create function f() returns int language sql as $$ SELECT 1 $$\n";
+        let original = token_snapshots(source, &mode).unwrap();
+
+        let reformatted =
+            "create function f()\nreturns int\nlanguage sql\nas $$\n    select 1\n$$\n";
+        assert!(safety_check(&original, reformatted, &mode).is_ok());
+
+        let changed = "create function f()\nreturns int\nlanguage sql\nas $$\n    select 2\n$$\n";
+        assert!(matches!(
+            safety_check(&original, changed, &mode),
+            Err(SqlfmtError::Equivalence(_))
+        ));
+    }
+
     #[test]
     fn test_format_bracket_error() {
         let mode = Mode::default();
@@ -656,7 +1024,7 @@ mod tests {
         std::fs::write(&sql_file, "SELECT 1\n").unwrap();
 
         let mode = Mode::default();
-        let paths = get_matching_paths(&[sql_file.clone()], &mode);
+        let paths = get_matching_paths(std::slice::from_ref(&sql_file), &mode);
         assert_eq!(paths.len(), 1);
         assert_eq!(paths[0], sql_file);
     }
@@ -680,8 +1048,10 @@ mod tests {
         std::fs::write(dir.path().join("a.sql"), "SELECT 1\n").unwrap();
         std::fs::write(dir.path().join("b.sql"), "SELECT 2\n").unwrap();
 
-        let mut mode = Mode::default();
-        mode.exclude = vec!["b.sql".to_string()];
+        let mode = Mode {
+            exclude: vec!["b.sql".to_string()],
+            ..Mode::default()
+        };
         let paths = get_matching_paths(&[dir.path().to_path_buf()], &mode);
         assert_eq!(paths.len(), 1);
     }
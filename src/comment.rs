@@ -1,3 +1,4 @@
+use crate::line::Indent;
 use crate::node::NodeIndex;
 use crate::token::Token;
 
@@ -81,8 +82,14 @@ impl Comment {
         }
     }
 
-    /// Render as standalone comment on its own line(s).
-    pub fn render_standalone(&self, prefix: &str, max_line_length: usize) -> String {
+    /// Render as standalone comment on its own line(s), wrapping plain text
+    /// to fit `max_line_length` after the `prefix` indentation.
+    pub fn render_standalone(
+        &self,
+        prefix: &str,
+        indent: Indent,
+        max_line_length: usize,
+    ) -> String {
         if self.is_multiline() || self.is_c_style() || self.is_jinja_comment() {
             return format!("{}{}\n", prefix, self.token.text.trim());
         }
@@ -94,7 +101,7 @@ impl Comment {
             return format!("{}{}\n", prefix, marker);
         }
 
-        let overhead = prefix.len() + marker.len() + 1; // +1 for space after marker
+        let overhead = indent.display_width(prefix) + marker.len() + 1; // +1 for space after marker
         let max_text_width = if max_line_length > overhead {
             max_line_length - overhead
         } else {
@@ -171,7 +178,10 @@ mod tests {
     #[test]
     fn test_standalone_rendering() {
         let c = make_comment("-- standalone", true);
-        assert_eq!(c.render_standalone("    ", 88), "    -- standalone\n");
+        assert_eq!(
+            c.render_standalone("    ", Indent::default(), 88),
+            "    -- standalone\n"
+        );
     }
 
     #[test]
@@ -209,7 +219,7 @@ mod tests {
         let c = make_comment("--", true);
         assert_eq!(c.marker(), "--");
         assert_eq!(c.body(), "");
-        assert_eq!(c.render_standalone("", 88), "--\n");
+        assert_eq!(c.render_standalone("", Indent::default(), 88), "--\n");
     }
 
     #[test]
@@ -224,7 +234,7 @@ mod tests {
         let c = make_comment("/* line1\n   line2 */", true);
         assert!(c.is_multiline());
         assert!(c.is_c_style());
-        let rendered = c.render_standalone("    ", 88);
+        let rendered = c.render_standalone("    ", Indent::default(), 88);
         assert!(rendered.starts_with("    "));
         assert!(rendered.contains("line1"));
     }
@@ -233,7 +243,7 @@ mod tests {
     fn test_wrap_long_comment() {
         let long_text = "-- this is a very long comment that should be wrapped because it exceeds the maximum line length limit of the formatter tool";
         let c = make_comment(long_text, true);
-        let rendered = c.render_standalone("", 40);
+        let rendered = c.render_standalone("", Indent::default(), 40);
         // Should produce multiple lines
         let line_count = rendered.lines().count();
         assert!(
@@ -249,7 +259,7 @@ mod tests {
         // Comments with Jinja expressions should NOT be wrapped
         let text = "-- depends_on: {{ ref('xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx') }}";
         let c = make_comment(text, true);
-        let rendered = c.render_standalone("", 88);
+        let rendered = c.render_standalone("", Indent::default(), 88);
         let line_count = rendered.lines().count();
         assert_eq!(
             line_count, 1,
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::dialect::dialect_from_name;
 use crate::error::SqlfmtError;
-use crate::mode::Mode;
+use crate::mode::{DialectConfig, Mode};
 
 /// Load sqlfmt configuration from a pyproject.toml file.
 /// Searches parent directories for pyproject.toml if no config path is given.
@@ -129,12 +130,117 @@ fn apply_config(mode: &mut Mode, config: &HashMap<String, toml::Value>) -> Resul
         mode.no_jinjafmt = *b;
     }
 
+    if let Some(toml::Value::Boolean(b)) = config.get("align_aliases") {
+        mode.align_aliases = *b;
+    }
+
+    if let Some(toml::Value::Boolean(b)) = config.get("explicit_aliases") {
+        mode.explicit_aliases = *b;
+    }
+
+    if let Some(toml::Value::Boolean(b)) = config.get("single_quote_strings") {
+        mode.single_quote_strings = *b;
+    }
+
+    if let Some(toml::Value::Boolean(b)) = config.get("remove_redundant_parens") {
+        mode.remove_redundant_parens = *b;
+    }
+
+    if let Some(toml::Value::Boolean(b)) = config.get("case_sensitive_names") {
+        mode.case_sensitive_names = Some(*b);
+    }
+
+    if let Some(value) = config.get("keyword_case") {
+        mode.keyword_case = value
+            .clone()
+            .try_into()
+            .map_err(|e| SqlfmtError::Config(format!("Invalid keyword_case: {}", e)))?;
+    }
+
+    if let Some(value) = config.get("comma_style") {
+        mode.comma_style = value
+            .clone()
+            .try_into()
+            .map_err(|e| SqlfmtError::Config(format!("Invalid comma_style: {}", e)))?;
+    }
+
+    if let Some(value) = config.get("normalize_operators") {
+        mode.normalize_operators = value
+            .clone()
+            .try_into()
+            .map_err(|e| SqlfmtError::Config(format!("Invalid normalize_operators: {}", e)))?;
+    }
+
+    if let Some(value) = config.get("indent_width") {
+        mode.indent_width = match value {
+            toml::Value::Integer(n) if *n > 0 => *n as usize,
+            _ => {
+                return Err(SqlfmtError::Config(format!(
+                    "Invalid indent_width: expected a positive integer, found {}",
+                    value
+                )))
+            }
+        };
+    }
+
+    if let Some(value) = config.get("indent_style") {
+        mode.indent_style = value
+            .clone()
+            .try_into()
+            .map_err(|e| SqlfmtError::Config(format!("Invalid indent_style: {}", e)))?;
+    }
+
+    for (key, case) in [
+        ("identifier_case", &mut mode.identifier_case),
+        ("function_case", &mut mode.function_case),
+    ] {
+        if let Some(value) = config.get(key) {
+            *case = Some(
+                value
+                    .clone()
+                    .try_into()
+                    .map_err(|e| SqlfmtError::Config(format!("Invalid {}: {}", key, e)))?,
+            );
+        }
+    }
+
+    if let Some(toml::Value::Table(dialects)) = config.get("dialects") {
+        for (name, table) in dialects {
+            let dialect: DialectConfig = table
+                .clone()
+                .try_into()
+                .map_err(|e| SqlfmtError::Config(format!("Invalid dialect {}: {}", name, e)))?;
+            if dialect_from_name(name).is_ok() {
+                return Err(SqlfmtError::Config(format!(
+                    "Dialect {} is built in; choose another name",
+                    name
+                )));
+            }
+            // Only built-in dialects can be extended
+            dialect_from_name(&dialect.base)?;
+            mode.dialects.insert(name.to_ascii_lowercase(), dialect);
+        }
+    }
+
     let known_keys = [
         "line_length",
         "dialect",
         "exclude",
         "no_jinjafmt",
         "encoding",
+        "dialects",
+        "case_sensitive_names",
+        "keyword_case",
+        "identifier_case",
+        "function_case",
+        "comma_style",
+        "indent_width",
+        "indent_style",
+        "align_aliases",
+        "explicit_aliases",
+        "normalize_operators",
+        "single_quote_strings",
+        "remove_redundant_parens",
     ];
     for key in config.keys() {
         if !known_keys.contains(&key.as_str()) {
@@ -168,9 +274,23 @@ mod tests {
             "dialect".to_string(),
             toml::Value::String("duckdb".to_string()),
         );
+        config.insert("align_aliases".to_string(), toml::Value::Boolean(true));
+        config.insert("explicit_aliases".to_string(), toml::Value::Boolean(true));
+        config.insert(
+            "single_quote_strings".to_string(),
+            toml::Value::Boolean(true),
+        );
+        config.insert(
+            "remove_redundant_parens".to_string(),
+            toml::Value::Boolean(true),
+        );
 
         apply_config(&mut mode, &config).unwrap();
         assert_eq!(mode.line_length, 120);
+        assert!(mode.align_aliases);
+        assert!(mode.explicit_aliases);
+        assert!(mode.single_quote_strings);
+        assert!(mode.remove_redundant_parens);
         assert_eq!(mode.dialect_name, "duckdb");
     }
 
@@ -288,4 +408,135 @@ mod tests {
         apply_config(&mut mode, &config).unwrap();
         assert_eq!(mode.dialect_name, "clickhouse");
     }
+
+    #[test]
+    fn test_apply_config_dialect_trino() {
+        let mut mode = Mode::default();
+        let mut config = HashMap::new();
+        config.insert(
+            "dialect".to_string(),
+            toml::Value::String("trino".to_string()),
+        );
+
+        apply_config(&mut mode, &config).unwrap();
+        assert_eq!(mode.dialect_name, "trino");
+        assert!(mode.dialect().is_ok());
+    }
+
+    #[test]
+    fn test_apply_config_name_cases() {
+        let mut mode = Mode::default();
+        let mut config = HashMap::new();
+        config.insert(
+            "keyword_case".to_string(),
+            toml::Value::String("upper".to_string()),
+        );
+        apply_config(&mut mode, &config).unwrap();
+        assert_eq!(mode.keyword_case, crate::mode::Case::Upper);
+        assert_eq!(mode.identifier_case, None);
+
+        config.insert(
+            "keyword_case".to_string(),
+            toml::Value::String("title".to_string()),
+        );
+        assert!(apply_config(&mut mode, &config).is_err());
+
+        let mut config = HashMap::new();
+        config.insert(
+            "function_case".to_string(),
+            toml::Value::String("preserve".to_string()),
+        );
+        apply_config(&mut mode, &config).unwrap();
+        assert_eq!(mode.function_case, Some(crate::mode::Case::Preserve));
+    }
+
+    #[test]
+    fn test_apply_config_comma_style() {
+        let mut mode = Mode::default();
+        let mut config = HashMap::new();
+        config.insert(
+            "comma_style".to_string(),
+            toml::Value::String("trailing".to_string()),
+        );
+        apply_config(&mut mode, &config).unwrap();
+        assert_eq!(mode.comma_style, crate::mode::CommaStyle::Trailing);
+
+        config.insert("comma_style".to_string(), toml::Value::Boolean(true));
+        assert!(apply_config(&mut mode, &config).is_err());
+    }
+
+    #[test]
+    fn test_load_config_normalize_operators() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("sqlfmt.toml");
+        std::fs::write(
+            &config_path,
+            "[normalize_operators]\nnot_equal = \"!=\"\ndouble_equals = true\n",
+        )
+        .unwrap();
+        let mode = load_config(&[], Some(config_path.as_path())).unwrap();
+        assert_eq!(
+            mode.normalize_operators.not_equal,
+            Some(crate::mode::NotEqual::BangEquals)
+        );
+        assert!(mode.normalize_operators.double_equals);
+
+        std::fs::write(&config_path, "[normalize_operators]\nnot_equal = \"=/=\"\n").unwrap();
+        assert!(load_config(&[], Some(config_path.as_path())).is_err());
+    }
+
+    #[test]
+    fn test_apply_config_indent() {
+        let mut mode = Mode::default();
+        let mut config = HashMap::new();
+        config.insert("indent_width".to_string(), toml::Value::Integer(2));
+        config.insert(
+            "indent_style".to_string(),
+            toml::Value::String("tabs".to_string()),
+        );
+        apply_config(&mut mode, &config).unwrap();
+        assert_eq!(mode.indent_width, 2);
+        assert_eq!(mode.indent_style, crate::mode::IndentStyle::Tabs);
+
+        config.insert("indent_width".to_string(), toml::Value::Integer(0));
+        assert!(apply_config(&mut mode, &config).is_err());
+    }
+
+    #[test]
+    fn test_load_config_custom_dialect() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("sqlfmt.toml");
+        std::fs::write(
+            &config_path,
+            "dialect = \"Warehouse\"\n\
+             [dialects.warehouse]\n\
+             base = \"snowflake\"\n\
+             unterm_keywords = [\"sample by\"]\n\
+             word_operators = [\"overlaps\"]\n\
+             case_sensitive_names = true\n",
+        )
+        .unwrap();
+
+        let mode = load_config(&[], Some(config_path.as_path())).unwrap();
+        let custom = &mode.dialects["warehouse"];
+        assert_eq!(custom.base, "snowflake");
+        assert_eq!(custom.unterm_keywords, vec!["sample by"]);
+        assert!(custom.set_operators.is_empty());
+        assert_eq!(custom.case_sensitive_names, Some(true));
+        assert!(mode.dialect().unwrap().case_sensitive_names());
+    }
+
+    #[test]
+    fn test_apply_config_custom_dialect_errors() {
+        let table = |toml: &str| {
+            let value: toml::Value = toml::from_str(toml).unwrap();
+            let mut config = HashMap::new();
+            config.insert("dialects".to_string(), value);
+            apply_config(&mut Mode::default(), &config)
+        };
+        assert!(table("[mine]\nbase = \"duckdb\"").is_ok());
+        assert!(table("[mine]\nbase = \"oracle\"").is_err());
+        assert!(table("[mine]\nkeywords = [\"qualify\"]").is_err());
+        assert!(table("[postgres]\nbase = \"duckdb\"").is_err());
+    }
 }
@@ -1,5 +1,14 @@
+use std::sync::Arc;
+
 use crate::analyzer::Analyzer;
 use crate::error::SqlfmtError;
+use crate::lexer::{
+    CustomKeywords, Keywords, Syntax, ALL_DIALECT_KEYWORDS, CLICKHOUSE_UNTERM_KEYWORDS,
+    CLICKHOUSE_WORD_OPERATORS, DUCKDB_SET_OPERATORS, DUCKDB_UNTERM_KEYWORDS, MYSQL_UNTERM_KEYWORDS,
+    SNOWFLAKE_RESERVED_WORDS, SNOWFLAKE_UNTERM_KEYWORDS, SPARK_RESERVED_WORDS,
+    SPARK_UNTERM_KEYWORDS, TSQL_UNTERM_KEYWORDS,
+};
+use crate::mode::DialectConfig;
 use crate::node_manager::NodeManager;
 
 /// A SQL dialect defines configuration for a specific SQL variant.
@@ -9,31 +18,322 @@ pub trait Dialect: Send + Sync {
         false
     }
 
+    /// Keywords, operators and reserved words this dialect adds to the ones
+    /// every dialect shares. Defaults to the keywords of all dialects.
+    fn keywords(&self) -> Keywords {
+        ALL_DIALECT_KEYWORDS.clone()
+    }
+
+    /// The dialect's keywords plus any dialect-specific tokens it lexes.
+    fn syntax(&self) -> Syntax {
+        Syntax::new(self.keywords())
+    }
+
     /// Create an analyzer configured for this dialect.
     fn initialize_analyzer(&self, line_length: usize) -> Analyzer {
         let nm = NodeManager::new(self.case_sensitive_names());
-        Analyzer::new(nm, line_length)
+        Analyzer::new(nm, self.syntax(), line_length)
     }
 }
 
 /// The default dialect. Covers common usage across Snowflake, DuckDB,
-/// PostgreSQL, MySQL, BigQuery, and SparkSQL.
+/// PostgreSQL, and SparkSQL. `#` is an operator here, so files with `#`
+/// comments need the mysql or bigquery dialect.
 pub struct Polyglot;
 
 impl Dialect for Polyglot {}
 
-/// ClickHouse dialect: same rules as Polyglot.
-/// Note: ClickHouse identifiers are technically case-sensitive at the engine level,
-/// but Python sqlfmt lowercases them like all other dialects.
+/// ClickHouse dialect: adds `prewhere`, `global`/`any` joins and `global in`.
+/// ClickHouse identifiers and function names are case-sensitive, so names
+/// keep their case (`toStartOfMonth` stays camelCase).
 pub struct ClickHouse;
 
-impl Dialect for ClickHouse {}
+static CLICKHOUSE_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[&CLICKHOUSE_UNTERM_KEYWORDS],
+    word_operators: &[&CLICKHOUSE_WORD_OPERATORS],
+    set_operators: &[],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for ClickHouse {
+    fn case_sensitive_names(&self) -> bool {
+        true
+    }
+
+    fn keywords(&self) -> Keywords {
+        CLICKHOUSE_KEYWORDS.clone()
+    }
+}
 
-/// DuckDB dialect: same as Polyglot for now; can be extended with DuckDB-specific
-/// keywords and syntax.
+/// DuckDB dialect: adds `positional join` and `union by name`.
 pub struct DuckDb;
 
-impl Dialect for DuckDb {}
+static DUCKDB_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[&DUCKDB_UNTERM_KEYWORDS],
+    word_operators: &[],
+    set_operators: &[&DUCKDB_SET_OPERATORS],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for DuckDb {
+    fn keywords(&self) -> Keywords {
+        DUCKDB_KEYWORDS.clone()
+    }
+}
+
+/// Snowflake dialect: adds MATCH_RECOGNIZE clauses, stage references
+/// (`@stage/path/`), the `->>` flow operator, and formats COPY INTO,
+/// CREATE STAGE / PIPE / STREAM / TASK and Snowflake Scripting blocks.
+pub struct Snowflake;
+
+static SNOWFLAKE_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[&SNOWFLAKE_UNTERM_KEYWORDS],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[&SNOWFLAKE_RESERVED_WORDS],
+    custom: None,
+};
+
+impl Dialect for Snowflake {
+    fn keywords(&self) -> Keywords {
+        SNOWFLAKE_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            stage_references: true,
+            dollar_names: true,
+            flow_operator: true,
+            stage_statements: true,
+            scripting_blocks: true,
+            declare_sections: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// PostgreSQL dialect: lexes Postgres operators (`~~*`, `>>=`, `#`) and
+/// standard strings, where backslash only escapes inside `E'...'`.
+pub struct Postgres;
+
+static POSTGRES_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for Postgres {
+    fn keywords(&self) -> Keywords {
+        POSTGRES_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            postgres_operators: true,
+            standard_strings: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// BigQuery dialect: lexes `"..."` as a string, raw and bytes literals
+/// (`r'...'`, `b'...'`) as single tokens, and `#` comments, and formats
+/// procedural blocks.
+/// Backtick names keep their case.
+pub struct BigQuery;
+
+static BIGQUERY_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for BigQuery {
+    fn keywords(&self) -> Keywords {
+        BIGQUERY_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            string_prefixes: true,
+            double_quoted_strings: true,
+            hash_comments: true,
+            scripting_blocks: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// Databricks (Spark SQL) dialect: adds `lateral view`, `distribute by`,
+/// Delta `optimize ... zorder by`, and `${name}` widget references.
+pub struct Databricks;
+
+static DATABRICKS_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[&SPARK_UNTERM_KEYWORDS],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[&SPARK_RESERVED_WORDS],
+    custom: None,
+};
+
+impl Dialect for Databricks {
+    fn keywords(&self) -> Keywords {
+        DATABRICKS_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            widget_references: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// Trino (and Athena) dialect: standard strings, where backslash is an
+/// ordinary character, and `U&'...'` Unicode strings.
+pub struct Trino;
+
+static TRINO_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for Trino {
+    fn keywords(&self) -> Keywords {
+        TRINO_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            standard_strings: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// T-SQL dialect (SQL Server, Azure Synapse): `[name]` identifiers,
+/// `@@rowcount` system functions, `GO` batch separators and
+/// `BEGIN ... END` control flow.
+pub struct Tsql;
+
+static TSQL_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[&TSQL_UNTERM_KEYWORDS],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for Tsql {
+    fn keywords(&self) -> Keywords {
+        TSQL_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            bracket_identifiers: true,
+            system_variables: true,
+            batch_separator: true,
+            select_top: true,
+            tsql_blocks: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// MySQL / MariaDB dialect: `#` comments, `"..."` strings, `straight_join`,
+/// `limit <offset>, <count>`, `:=` assignments, and `DELIMITER` lines that
+/// change the statement terminator.
+pub struct MySql;
+
+static MYSQL_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[&MYSQL_UNTERM_KEYWORDS],
+    word_operators: &[],
+    set_operators: &[],
+    reserved_words: &[],
+    custom: None,
+};
+
+impl Dialect for MySql {
+    fn keywords(&self) -> Keywords {
+        MYSQL_KEYWORDS.clone()
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            double_quoted_strings: true,
+            hash_comments: true,
+            delimiter_statements: true,
+            limit_offset_count: true,
+            assignment_operator: true,
+            ..Syntax::new(self.keywords())
+        }
+    }
+}
+
+/// A user-defined dialect: a built-in dialect with the extra keywords of a
+/// `[tool.sqlfmt.dialects.<name>]` table.
+pub struct CustomDialect {
+    base: Box<dyn Dialect>,
+    keywords: Arc<CustomKeywords>,
+    case_sensitive_names: Option<bool>,
+}
+
+impl CustomDialect {
+    pub fn new(config: &DialectConfig) -> Result<Self, SqlfmtError> {
+        let normalize = |words: &[String]| {
+            words
+                .iter()
+                .map(|w| {
+                    w.split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .to_ascii_lowercase()
+                })
+                .collect()
+        };
+        Ok(Self {
+            base: dialect_from_name(&config.base)?,
+            keywords: Arc::new(CustomKeywords {
+                unterm_keywords: normalize(&config.unterm_keywords),
+                word_operators: normalize(&config.word_operators),
+                set_operators: normalize(&config.set_operators),
+                reserved_words: normalize(&config.reserved_words),
+            }),
+            case_sensitive_names: config.case_sensitive_names,
+        })
+    }
+}
+
+impl Dialect for CustomDialect {
+    fn case_sensitive_names(&self) -> bool {
+        self.case_sensitive_names
+            .unwrap_or_else(|| self.base.case_sensitive_names())
+    }
+
+    fn keywords(&self) -> Keywords {
+        Keywords {
+            custom: Some(Arc::clone(&self.keywords)),
+            ..self.base.keywords()
+        }
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax {
+            keywords: self.keywords(),
+            ..self.base.syntax()
+        }
+    }
+}
 
 /// Create a dialect from a string name.
 pub fn dialect_from_name(name: &str) -> Result<Box<dyn Dialect>, SqlfmtError> {
@@ -41,6 +341,13 @@ pub fn dialect_from_name(name: &str) -> Result<Box<dyn Dialect>, SqlfmtError> {
         "polyglot" => Ok(Box::new(Polyglot)),
         "clickhouse" => Ok(Box::new(ClickHouse)),
         "duckdb" => Ok(Box::new(DuckDb)),
+        "snowflake" => Ok(Box::new(Snowflake)),
+        "postgres" | "postgresql" => Ok(Box::new(Postgres)),
+        "bigquery" => Ok(Box::new(BigQuery)),
+        "databricks" | "spark" | "sparksql" => Ok(Box::new(Databricks)),
+        "trino" | "athena" => Ok(Box::new(Trino)),
+        "tsql" | "mssql" | "sqlserver" => Ok(Box::new(Tsql)),
+        "mysql" | "mariadb" => Ok(Box::new(MySql)),
         _ => Err(SqlfmtError::Config(format!("Unknown dialect: {}", name))),
     }
 }
@@ -58,7 +365,7 @@ mod tests {
     #[test]
     fn test_clickhouse_dialect() {
         let dialect = ClickHouse;
-        assert!(!dialect.case_sensitive_names());
+        assert!(dialect.case_sensitive_names());
     }
 
     #[test]
@@ -67,11 +374,140 @@ mod tests {
         assert!(!dialect.case_sensitive_names());
     }
 
+    #[test]
+    fn test_dialect_keywords() {
+        let clickhouse = ClickHouse.keywords();
+        assert!(clickhouse.is_unterm_keyword("prewhere"));
+        assert!(clickhouse.is_word_operator("global in"));
+        assert!(!clickhouse.is_set_operator("union by name"));
+
+        let duckdb = DuckDb.keywords();
+        assert!(duckdb.is_set_operator("union all by name"));
+        assert!(!duckdb.is_unterm_keyword("prewhere"));
+        assert!(!duckdb.is_unterm_keyword("lateral view"));
+
+        let polyglot = Polyglot.keywords();
+        assert!(polyglot.is_unterm_keyword("prewhere"));
+        assert!(polyglot.is_unterm_keyword("lateral view"));
+        assert!(polyglot.is_reserved_word("put"));
+        assert!(!polyglot.is_unterm_keyword("measures"));
+
+        let snowflake = Snowflake.keywords();
+        assert!(snowflake.is_unterm_keyword("measures"));
+        assert!(snowflake.is_reserved_word("put"));
+        assert!(!snowflake.is_unterm_keyword("prewhere"));
+    }
+
+    #[test]
+    fn test_snowflake_syntax() {
+        let syntax = Snowflake.syntax();
+        assert!(syntax.stage_references && syntax.flow_operator && syntax.stage_statements);
+        let syntax = Polyglot.syntax();
+        assert!(!syntax.stage_references && !syntax.flow_operator && !syntax.stage_statements);
+    }
+
+    #[test]
+    fn test_postgres_syntax() {
+        let syntax = Postgres.syntax();
+        assert!(syntax.postgres_operators && syntax.standard_strings);
+        assert!(!syntax.keywords.is_unterm_keyword("prewhere"));
+        let syntax = Polyglot.syntax();
+        assert!(!syntax.postgres_operators && !syntax.standard_strings);
+    }
+
+    #[test]
+    fn test_databricks_syntax() {
+        let syntax = Databricks.syntax();
+        assert!(syntax.widget_references);
+        assert!(syntax.keywords.is_unterm_keyword("zorder by"));
+        assert!(syntax.keywords.is_reserved_word("cache"));
+        assert!(!syntax.keywords.is_unterm_keyword("prewhere"));
+        assert!(!Polyglot.syntax().widget_references);
+    }
+
+    #[test]
+    fn test_trino_syntax() {
+        let syntax = Trino.syntax();
+        assert!(syntax.standard_strings && !syntax.postgres_operators);
+        assert!(!syntax.keywords.is_unterm_keyword("lateral view"));
+        assert!(!Trino.case_sensitive_names());
+    }
+
+    #[test]
+    fn test_tsql_syntax() {
+        let syntax = Tsql.syntax();
+        assert!(syntax.bracket_identifiers && syntax.system_variables);
+        assert!(syntax.batch_separator && syntax.tsql_blocks);
+        assert!(syntax.keywords.is_unterm_keyword("output"));
+        assert!(!Polyglot.syntax().keywords.is_unterm_keyword("output"));
+        assert!(!Tsql.case_sensitive_names());
+    }
+
+    #[test]
+    fn test_mysql_syntax() {
+        let syntax = MySql.syntax();
+        assert!(syntax.hash_comments && syntax.delimiter_statements);
+        assert!(syntax.limit_offset_count && syntax.assignment_operator);
+        assert!(syntax.keywords.is_unterm_keyword("straight_join"));
+        assert!(!Polyglot.syntax().hash_comments);
+        assert!(BigQuery.syntax().hash_comments && !ClickHouse.syntax().hash_comments);
+        assert!(!Snowflake.syntax().hash_comments && !Postgres.syntax().hash_comments);
+        assert!(!Polyglot.syntax().delimiter_statements);
+        assert!(!Polyglot.syntax().limit_offset_count && !Polyglot.syntax().assignment_operator);
+    }
+
+    #[test]
+    fn test_custom_dialect() {
+        let config = DialectConfig {
+            base: "ClickHouse".to_string(),
+            unterm_keywords: vec!["Sample   By".to_string()],
+            ..DialectConfig::default()
+        };
+        let dialect = CustomDialect::new(&config).unwrap();
+        assert!(dialect.case_sensitive_names());
+        let syntax = dialect.syntax();
+        assert!(!syntax.hash_comments);
+        assert!(syntax.keywords.is_unterm_keyword("sample by"));
+        assert!(syntax.keywords.is_unterm_keyword("prewhere"));
+
+        let config = DialectConfig {
+            case_sensitive_names: Some(false),
+            ..config
+        };
+        assert!(!CustomDialect::new(&config).unwrap().case_sensitive_names());
+        let config = DialectConfig {
+            base: "oracle".to_string(),
+            ..config
+        };
+        assert!(CustomDialect::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_bigquery_syntax() {
+        let syntax = BigQuery.syntax();
+        assert!(syntax.string_prefixes && syntax.double_quoted_strings);
+        assert!(!BigQuery.case_sensitive_names());
+        let syntax = Polyglot.syntax();
+        assert!(!syntax.string_prefixes && !syntax.double_quoted_strings);
+    }
+
     #[test]
     fn test_dialect_from_name() {
         assert!(dialect_from_name("polyglot").is_ok());
         assert!(dialect_from_name("clickhouse").is_ok());
         assert!(dialect_from_name("duckdb").is_ok());
+        assert!(dialect_from_name("snowflake").is_ok());
+        assert!(dialect_from_name("postgres").is_ok());
+        assert!(dialect_from_name("postgresql").is_ok());
+        assert!(dialect_from_name("bigquery").is_ok());
+        assert!(dialect_from_name("databricks").is_ok());
+        assert!(dialect_from_name("spark").is_ok());
+        assert!(dialect_from_name("trino").is_ok());
+        assert!(dialect_from_name("athena").is_ok());
+        assert!(dialect_from_name("tsql").is_ok());
+        assert!(dialect_from_name("sqlserver").is_ok());
+        assert!(dialect_from_name("mysql").is_ok());
+        assert!(dialect_from_name("mariadb").is_ok());
         assert!(dialect_from_name("unknown").is_err());
     }
 
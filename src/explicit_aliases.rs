@@ -0,0 +1,154 @@
+use compact_str::CompactString;
+
+use crate::mode::Case;
+use crate::node::Node;
+use crate::query::Query;
+use crate::token::{Token, TokenType};
+
+/// Names that can follow a table or expression without being an alias.
+const NOT_ALIASES: &[&str] = &["final", "precision", "varying", "zone"];
+
+/// Words that take the name after them, as in `a collate "C"` or
+/// `for system_time as of ts`.
+const BEFORE_NOT_ALIASES: &[&str] = &["collate", "of"];
+
+/// Words that continue a from item rather than alias it, as in
+/// `unnest(arr) with ordinality` or `t for system_time as of ts`.
+const CLAUSE_CONTINUATIONS: &[&str] = &["with", "for"];
+
+/// Insert `as` between an expression and a bare alias that ends a select
+/// list item or a from/join item, so `select a b from t x` becomes
+/// `select a as b from t as x`.
+pub(crate) fn insert_explicit_aliases(
+    query: &mut Query,
+    arena: &mut Vec<Node>,
+    keyword_case: Case,
+) {
+    // (line, position in line) of every content node, in order
+    let mut content: Vec<(usize, usize)> = Vec::new();
+    for (li, line) in query.lines.iter().enumerate() {
+        for (pos, &idx) in line.nodes.iter().enumerate() {
+            if !arena[idx].is_newline() {
+                content.push((li, pos));
+            }
+        }
+    }
+    let node_at = |k: usize| query.lines[content[k].0].nodes[content[k].1];
+
+    let mut aliases = Vec::new();
+    for k in 1..content.len() {
+        let next = (k + 1 < content.len()).then(|| node_at(k + 1));
+        if is_bare_alias(arena, node_at(k - 1), node_at(k), next) {
+            aliases.push(content[k]);
+        }
+    }
+
+    let value = match keyword_case {
+        Case::Upper => "AS",
+        _ => "as",
+    };
+    for &(li, pos) in aliases.iter().rev() {
+        let alias_idx = query.lines[li].nodes[pos];
+        let alias = &arena[alias_idx];
+        let token = Token::new(
+            TokenType::WordOperator,
+            " ",
+            "as",
+            alias.token.spos,
+            alias.token.spos,
+        );
+        let as_node = Node::new(
+            token,
+            alias.previous_node,
+            CompactString::const_new(" "),
+            CompactString::const_new(value),
+            alias.bracket_depth,
+            alias.jinja_depth,
+        );
+        let as_idx = arena.len();
+        arena.push(as_node);
+        arena[alias_idx].previous_node = Some(as_idx);
+        arena[alias_idx].prefix = CompactString::const_new(" ");
+        query.lines[li].nodes.insert(pos, as_idx);
+    }
+}
+
+/// True if `alias` is a bare alias for the expression or table ending at
+/// `prev`: a name at the same depth that ends its item (`next` is a comma,
+/// clause keyword, closing bracket, ...), inside a select list or a
+/// from/join clause. The clause is the keyword found by following
+/// `previous_node` back to the first shallower node.
+fn is_bare_alias(arena: &[Node], prev: usize, alias: usize, next: Option<usize>) -> bool {
+    let (p, a) = (&arena[prev], &arena[alias]);
+    // Names are lexed with strings: `date '2024-01-01'` has no alias
+    let is_name = matches!(a.token.token_type, TokenType::Name | TokenType::QuotedName)
+        && !a.value.ends_with(['\'', '$']);
+    // `{{ prefix }}_name` is one name
+    let is_separate =
+        !a.token.prefix.is_empty() || a.previous_node.is_some_and(|i| arena[i].is_newline());
+    if !is_name
+        || !is_separate
+        || a.formatting_disabled
+        || p.formatting_disabled
+        || a.depth() != p.depth()
+        || NOT_ALIASES.contains(&a.value.to_ascii_lowercase().as_str())
+        || BEFORE_NOT_ALIASES.contains(&p.value.to_ascii_lowercase().as_str())
+        || a.value
+            .split_whitespace()
+            .next()
+            .is_some_and(|w| CLAUSE_CONTINUATIONS.contains(&w.to_ascii_lowercase().as_str()))
+    {
+        return false;
+    }
+    let ends_expression = matches!(
+        p.token.token_type,
+        TokenType::Name | TokenType::QuotedName | TokenType::Number | TokenType::JinjaExpression
+    ) || p.is_closing_bracket();
+    let ends_item = next.is_none_or(|q| {
+        let q = &arena[q];
+        q.is_closing_bracket()
+            || q.is_jinja() && !q.token.token_type.is_jinja_statement()
+            || matches!(
+                q.token.token_type,
+                TokenType::Comma
+                    | TokenType::Semicolon
+                    | TokenType::UntermKeyword
+                    | TokenType::SetOperator
+                    | TokenType::On
+            )
+    });
+    if !ends_expression || !ends_item {
+        return false;
+    }
+
+    let mut before_prev = None;
+    let mut cur = Some(prev);
+    while let Some(i) = cur {
+        let node = &arena[i];
+        if node.is_newline() {
+            cur = node.previous_node;
+            continue;
+        }
+        if node.bracket_depth < a.bracket_depth {
+            let kw = node.value.to_ascii_lowercase();
+            return node.is_unterm_keyword()
+                && (kw.starts_with("select")
+                    || kw.ends_with("join")
+                    || kw == "from"
+                    || kw == "lateral")
+                // `interval 1 day` is not `1 as day`
+                && !before_prev.is_some_and(|b: &Node| {
+                    b.value.eq_ignore_ascii_case("interval")
+                });
+        }
+        // `select distinct on (a) a` and join conditions
+        if node.token.token_type == TokenType::On {
+            return false;
+        }
+        if i != prev && before_prev.is_none() {
+            before_prev = Some(node);
+        }
+        cur = node.previous_node;
+    }
+    false
+}
@@ -1,8 +1,9 @@
 use compact_str::CompactString;
 
 use crate::jinja_formatter::JinjaFormatter;
-use crate::line::Line;
+use crate::line::{indent_str, Indent, Line};
 use crate::merger::LineMerger;
+use crate::mode::{CommaStyle, Mode};
 use crate::node::Node;
 use crate::query::Query;
 use crate::splitter::LineSplitter;
@@ -16,14 +17,22 @@ use crate::token::TokenType;
 ///   5. Remove extra blank lines
 pub struct QueryFormatter {
     line_length: usize,
+    indent: Indent,
     no_jinjafmt: bool,
+    comma_style: CommaStyle,
+    align_aliases: bool,
+    assignment_operator: bool,
 }
 
 impl QueryFormatter {
-    pub fn new(line_length: usize, no_jinjafmt: bool) -> Self {
+    pub fn new(mode: &Mode) -> Self {
         Self {
-            line_length,
-            no_jinjafmt,
+            line_length: mode.line_length,
+            indent: mode.indent(),
+            no_jinjafmt: mode.no_jinjafmt,
+            comma_style: mode.comma_style,
+            align_aliases: mode.align_aliases,
+            assignment_operator: mode.dialect().is_ok_and(|d| d.syntax().assignment_operator),
         }
     }
 
@@ -31,6 +40,10 @@ impl QueryFormatter {
     pub fn format(&self, query: &mut Query, arena: &mut Vec<Node>) {
         self.split_lines(query, arena);
 
+        if self.comma_style == CommaStyle::Trailing {
+            self.lead_commas(query, arena);
+        }
+
         if !self.no_jinjafmt {
             self.format_jinja(query, arena);
         }
@@ -44,6 +57,14 @@ impl QueryFormatter {
 
         self.merge_lines(query, arena);
 
+        if self.comma_style == CommaStyle::Trailing {
+            self.trail_commas(query, arena);
+        }
+
+        if self.align_aliases {
+            self.align_select_lists(query, arena);
+        }
+
         self.remove_extra_blank_lines(query, arena);
     }
 
@@ -58,9 +79,55 @@ impl QueryFormatter {
         query.lines = new_lines;
     }
 
+    /// Stage 1b (trailing-comma style): join each standalone comma, as split
+    /// from `a,` in the source, to the next content line, and hand its
+    /// inline comment (`a,  -- note`) back to the previous line. Lists are
+    /// then laid out exactly as from leading-comma source, so formatting our
+    /// own trailing-comma output is idempotent.
+    fn lead_commas(&self, query: &mut Query, arena: &[Node]) {
+        let lines = &mut query.lines;
+        let mut remove = vec![false; lines.len()];
+        let mut prev_content: Option<usize> = None;
+
+        for i in 0..lines.len() {
+            let line = &lines[i];
+            if line.is_blank_line(arena) || line.is_standalone_comment_line(arena) {
+                continue;
+            }
+            let comments_movable = line.comments.is_empty()
+                || (line.comments.iter().all(|c| c.is_inline())
+                    && prev_content.is_some_and(|j| lines[j].comments.is_empty()));
+            let next = (i + 1..lines.len()).find(|&k| {
+                !lines[k].is_blank_line(arena) && !lines[k].is_standalone_comment_line(arena)
+            });
+            let Some(next) = next.filter(|&k| {
+                line.is_standalone_comma(arena)
+                    && comments_movable
+                    && !line.has_formatting_disabled()
+                    && !lines[k].has_formatting_disabled()
+                    && !is_jinja_block_line(&lines[k], arena)
+            }) else {
+                prev_content = Some(i);
+                continue;
+            };
+            let comma = lines[i]
+                .first_content_node_idx(arena)
+                .expect("a standalone comma line has content");
+            let comments = std::mem::take(&mut lines[i].comments);
+            if let Some(j) = prev_content {
+                lines[j].comments = comments;
+            }
+            lines[next].nodes.insert(0, comma);
+            lines[next].previous_node = arena[comma].previous_node;
+            remove[i] = true;
+        }
+
+        remove_marked_lines(lines, &remove);
+    }
+
     /// Stage 2: Format Jinja templates.
     fn format_jinja(&self, query: &mut Query, arena: &mut [Node]) {
-        let formatter = JinjaFormatter::new(self.line_length);
+        let formatter = JinjaFormatter::new(self.line_length, self.indent);
         for line in &mut query.lines {
             if !line.has_formatting_disabled() {
                 formatter.format_line(line, arena);
@@ -85,7 +152,7 @@ impl QueryFormatter {
 
             let analysis = analyze_multiline_jinja(&line, arena);
 
-            if !analysis.needs_split(self.line_length, &line, arena) {
+            if !analysis.needs_split(self.line_length, self.indent, &line, arena) {
                 new_lines.push(line);
                 continue;
             }
@@ -132,10 +199,186 @@ impl QueryFormatter {
 
     /// Stage 4: Merge short lines back together.
     fn merge_lines(&self, query: &mut Query, arena: &[Node]) {
-        let merger = LineMerger::new(self.line_length);
+        let merger = LineMerger::new(self.line_length, self.indent)
+            .with_assignment_operator(self.assignment_operator);
         query.lines = merger.maybe_merge_lines(&query.lines, arena);
     }
 
+    /// Stage 4b: Trailing-comma style. The splitter and merger lay lists out
+    /// with leading commas; move each line's leading comma to the end of the
+    /// previous content line, so `a\n, b` becomes `a,\nb`. Commas stay put
+    /// when that line is a Jinja block tag (moving the comma would change
+    /// which branch it belongs to) or has formatting disabled.
+    fn trail_commas(&self, query: &mut Query, arena: &[Node]) {
+        let lines = &mut query.lines;
+        let mut remove = vec![false; lines.len()];
+        let mut prev_content: Option<usize> = None;
+
+        for i in 0..lines.len() {
+            let line = &lines[i];
+            if line.is_blank_line(arena) || line.is_standalone_comment_line(arena) {
+                continue;
+            }
+            let standalone = line.is_standalone_comma(arena);
+            let movable = line.starts_with_comma(arena)
+                && !line.has_formatting_disabled()
+                && (!standalone || line.comments.is_empty())
+                && prev_content.is_some_and(|j| {
+                    !lines[j].has_formatting_disabled() && !is_jinja_block_line(&lines[j], arena)
+                });
+            if let (true, Some(j)) = (movable, prev_content) {
+                let pos = lines[i]
+                    .nodes
+                    .iter()
+                    .position(|&idx| !arena[idx].is_newline())
+                    .expect("a line starting with a comma has content");
+                let comma = lines[i].nodes.remove(pos);
+                let end = lines[j]
+                    .nodes
+                    .iter()
+                    .rposition(|&idx| !arena[idx].is_newline())
+                    .map_or(0, |p| p + 1);
+                lines[j].nodes.insert(end, comma);
+                if standalone {
+                    remove[i] = true;
+                    continue;
+                }
+            }
+            prev_content = Some(i);
+        }
+
+        remove_marked_lines(lines, &remove);
+    }
+
+    /// Stage 4c (align_aliases): within each run of consecutive select-list
+    /// items at the same depth, pad so that `as <alias>` and then inline
+    /// comments start at the same column. A run is broken by blank lines,
+    /// standalone comments, deeper lines and lines that close a bracket.
+    /// Either alignment is skipped for a run if a padded line would exceed
+    /// the line length.
+    fn align_select_lists(&self, query: &mut Query, arena: &mut [Node]) {
+        let lines = &query.lines;
+        let mut runs: Vec<Vec<usize>> = Vec::new();
+        // Item depth and current run of each enclosing select list.
+        let mut lists: Vec<((usize, usize), Vec<usize>)> = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            if line.is_blank_line(arena) || line.is_standalone_comment_line(arena) {
+                if let Some((_, run)) = lists.last_mut() {
+                    runs.push(std::mem::take(run));
+                }
+                continue;
+            }
+            let depth = line.depth(arena);
+            while lists.last().is_some_and(|(d, _)| depth < *d) {
+                let (_, run) = lists.pop().expect("checked above");
+                runs.push(run);
+            }
+            if let Some((d, run)) = lists.last_mut() {
+                let alignable = depth == *d
+                    && !line.has_formatting_disabled()
+                    && !line.closes_bracket_from_previous_line(arena)
+                    && !is_jinja_block_line(line, arena)
+                    && line
+                        .nodes
+                        .iter()
+                        .all(|&idx| arena[idx].is_newline() || !arena[idx].value.contains('\n'));
+                if alignable {
+                    run.push(i);
+                } else {
+                    runs.push(std::mem::take(run));
+                }
+            }
+            if let Some(node) = line.first_content_node(arena) {
+                if line.second_content_node(arena).is_none()
+                    && node.is_unterm_keyword()
+                    && node.value.to_ascii_lowercase().starts_with("select")
+                {
+                    let (sql, jinja) = node.depth();
+                    lists.push(((sql + 1, jinja), Vec::new()));
+                }
+            }
+        }
+        runs.extend(lists.into_iter().map(|(_, run)| run));
+
+        for run in runs.iter().filter(|run| run.len() > 1) {
+            self.align_aliases_in_run(&query.lines, run, arena);
+            self.align_comments_in_run(&mut query.lines, run, arena);
+        }
+    }
+
+    /// Pad the space before each `as <alias>` in the run to a common column.
+    fn align_aliases_in_run(&self, lines: &[Line], run: &[usize], arena: &mut [Node]) {
+        // (`as` node, columns before its prefix)
+        let mut aliases: Vec<(usize, usize)> = Vec::new();
+        for &i in run {
+            let line = &lines[i];
+            if let Some(pos) = alias_position(line, arena) {
+                let mut width = line.indent_size(arena, self.indent);
+                for (k, &idx) in line.nodes[..pos]
+                    .iter()
+                    .filter(|&&idx| !arena[idx].is_newline())
+                    .enumerate()
+                {
+                    width += if k == 0 {
+                        arena[idx].value.len()
+                    } else {
+                        arena[idx].len()
+                    };
+                }
+                aliases.push((line.nodes[pos], width));
+            }
+        }
+        if aliases.len() < 2 {
+            return;
+        }
+        let column = aliases.iter().map(|&(_, w)| w).max().unwrap_or(0) + 1;
+        let fits = run.iter().all(|&i| {
+            let line = &lines[i];
+            match aliases.iter().find(|(idx, _)| line.nodes.contains(idx)) {
+                Some(&(idx, width)) => {
+                    let extra = (column - width).saturating_sub(arena[idx].prefix.len());
+                    extra == 0 || line.len(arena, self.indent) + extra <= self.line_length
+                }
+                None => true,
+            }
+        });
+        if fits {
+            for &(idx, width) in &aliases {
+                arena[idx].prefix = CompactString::from(indent_str(column - width));
+            }
+        }
+    }
+
+    /// Pad the space before inline comments in the run to a common column.
+    fn align_comments_in_run(&self, lines: &mut [Line], run: &[usize], arena: &[Node]) {
+        let commented: Vec<usize> = run
+            .iter()
+            .copied()
+            .filter(|&i| {
+                !lines[i].comments.is_empty() && lines[i].comments.iter().all(|c| c.is_inline())
+            })
+            .collect();
+        if commented.len() < 2 {
+            return;
+        }
+        let column = commented
+            .iter()
+            .map(|&i| lines[i].len(arena, self.indent))
+            .max()
+            .unwrap_or(0);
+        let fits = commented.iter().all(|&i| {
+            let len = lines[i].len(arena, self.indent);
+            len == column
+                || column + lines[i].len_with_comments(arena, self.indent) - len <= self.line_length
+        });
+        if fits {
+            for &i in &commented {
+                lines[i].comment_padding = column - lines[i].len(arena, self.indent);
+            }
+        }
+    }
+
     /// Stage 5: Remove extra blank lines.
     /// At depth (0,0): max 2 consecutive blank lines.
     /// At any other depth: max 1 consecutive blank line.
@@ -193,7 +436,7 @@ struct MultilineJinjaAnalysis {
 }
 
 impl MultilineJinjaAnalysis {
-    fn needs_split(&self, max_length: usize, line: &Line, arena: &[Node]) -> bool {
+    fn needs_split(&self, max_length: usize, indent: Indent, line: &Line, arena: &[Node]) -> bool {
         if !self.has_multiline || self.content_count < 2 {
             return false;
         }
@@ -203,7 +446,7 @@ impl MultilineJinjaAnalysis {
         if self.first_content_is_on {
             return false;
         }
-        line.len(arena) > max_length
+        line.len(arena, indent) > max_length
     }
 }
 
@@ -287,6 +530,57 @@ fn split_line_at_jinja(line: Line, split_pos: usize, arena: &mut Vec<Node>) -> (
     (line1, line2)
 }
 
+/// True if the line starts with a Jinja block tag (`{% if %}`, `{% else %}`,
+/// `{% endif %}`, ...).
+fn is_jinja_block_line(line: &Line, arena: &[Node]) -> bool {
+    line.first_content_node(arena).is_some_and(|n| {
+        matches!(
+            n.token.token_type,
+            TokenType::JinjaBlockStart | TokenType::JinjaBlockKeyword | TokenType::JinjaBlockEnd
+        )
+    })
+}
+
+/// Position in `line.nodes` of a trailing `as` that introduces a column
+/// alias, as in `, amount * rate as total` or `amount as total,`.
+fn alias_position(line: &Line, arena: &[Node]) -> Option<usize> {
+    let content: Vec<usize> = (0..line.nodes.len())
+        .filter(|&p| !arena[line.nodes[p]].is_newline())
+        .collect();
+    let mut end = content.len();
+    if end > 0 && arena[line.nodes[content[end - 1]]].is_comma() {
+        end -= 1;
+    }
+    if end < 3 {
+        return None;
+    }
+    let as_pos = content[end - 2];
+    let as_node = &arena[line.nodes[as_pos]];
+    let alias = &arena[line.nodes[content[end - 1]]];
+    let first = &arena[line.nodes[content[0]]];
+    let is_alias = as_node.token.token_type == TokenType::WordOperator
+        && as_node.value.eq_ignore_ascii_case("as")
+        && as_node.depth() == line.depth(arena)
+        && matches!(
+            alias.token.token_type,
+            TokenType::Name | TokenType::QuotedName
+        );
+    // `, as x` has nothing to alias
+    let has_expression = end > 3 || !first.is_comma();
+    (is_alias && has_expression).then_some(as_pos)
+}
+
+/// Drop the lines marked in `remove`.
+fn remove_marked_lines(lines: &mut Vec<Line>, remove: &[bool]) {
+    if remove.contains(&true) {
+        let mut i = 0;
+        lines.retain(|_| {
+            i += 1;
+            !remove[i - 1]
+        });
+    }
+}
+
 /// Check if a line starts with a Jinja block start tag.
 fn is_jinja_block_start_line(line: &Line, arena: &[Node]) -> bool {
     line.first_content_node(arena)
@@ -337,15 +631,20 @@ fn adjust_bracket_depth(line: &Line, target_depth: usize, arena: &mut [Node]) {
 mod tests {
     use super::*;
     use crate::analyzer::Analyzer;
+    use crate::lexer::{Syntax, ALL_DIALECT_KEYWORDS};
     use crate::node_manager::NodeManager;
 
     fn format_sql(source: &str) -> (Query, Vec<Node>) {
+        format_sql_with(source, &Mode::default())
+    }
+
+    fn format_sql_with(source: &str, mode: &Mode) -> (Query, Vec<Node>) {
         let nm = NodeManager::new(false);
-        let mut analyzer = Analyzer::new(nm, 88);
+        let mut analyzer = Analyzer::new(nm, Syntax::new(ALL_DIALECT_KEYWORDS.clone()), 88);
         let mut query = analyzer.parse_query(source).unwrap();
         let mut arena = std::mem::take(&mut analyzer.arena);
 
-        let formatter = QueryFormatter::new(88, false);
+        let formatter = QueryFormatter::new(mode);
         formatter.format(&mut query, &mut arena);
 
         (query, arena)
@@ -483,4 +782,47 @@ mod tests {
         assert!(rendered.contains("{% if flag %}"));
         assert!(rendered.contains("{% endif %}"));
     }
+
+    #[test]
+    fn test_format_trailing_commas() {
+        let mode = Mode {
+            comma_style: CommaStyle::Trailing,
+            ..Mode::default()
+        };
+        let expected = "select\n    a,  -- first\n    -- last\n    b\nfrom t\n";
+        for source in [
+            "select a -- first\n-- last\n, b from t\n",
+            "select a, -- first\n-- last\nb from t\n",
+        ] {
+            let (query, arena) = format_sql_with(source, &mode);
+            assert_eq!(query.render(&arena), expected);
+        }
+    }
+
+    #[test]
+    fn test_format_align_aliases() {
+        let mode = Mode {
+            align_aliases: true,
+            line_length: 40,
+            ..Mode::default()
+        };
+        let (query, arena) = format_sql_with(
+            "select a as x, long_column_name as y -- note\n, c -- other\nfrom t\n",
+            &mode,
+        );
+        assert_eq!(
+            query.render(&arena),
+            "select\n    a                  as x\n    , long_column_name as y  -- note\n    , c                      -- other\nfrom t\n"
+        );
+
+        // Padding `a as a_much_longer_alias` would run past the line length
+        let (query, arena) = format_sql_with(
+            "select a as a_much_longer_alias, some_long_column_name as y from t\n",
+            &mode,
+        );
+        assert_eq!(
+            query.render(&arena),
+            "select\n    a as a_much_longer_alias\n    , some_long_column_name as y\nfrom t\n"
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use compact_str::CompactString;
 
-use crate::line::{indent_str, Line};
+use crate::line::{Indent, Line};
 use crate::node::Node;
 use crate::string_utils::{skip_string_literal, skip_string_literal_into};
 use crate::token::TokenType;
@@ -9,18 +9,19 @@ use crate::token::TokenType;
 /// long Jinja expressions across multiple lines (like Python's black formatter).
 pub struct JinjaFormatter {
     pub max_length: usize,
+    pub indent: Indent,
 }
 
 impl JinjaFormatter {
-    pub fn new(max_length: usize) -> Self {
-        Self { max_length }
+    pub fn new(max_length: usize, indent: Indent) -> Self {
+        Self { max_length, indent }
     }
 
     /// Format Jinja tags in a line.
     /// First normalizes whitespace, then applies multiline formatting
     /// if the line would exceed max_length.
     pub fn format_line(&self, line: &mut Line, arena: &mut [Node]) {
-        let base_indent = line.indent_size(arena);
+        let base_level = line.indent_level(arena);
 
         for &idx in &line.nodes {
             let node = &arena[idx];
@@ -48,7 +49,7 @@ impl JinjaFormatter {
         // always format as multiline (matching black's behavior).
         for &idx in &line.nodes {
             let node = &arena[idx];
-            let line_len = base_indent + node.value.len();
+            let line_len = self.indent.columns(base_level) + node.value.len();
             let has_magic_trailing_comma = has_trailing_comma_in_brackets(&node.value);
             if (line_len <= self.max_length && !has_magic_trailing_comma)
                 || node.value.contains('\n')
@@ -58,7 +59,7 @@ impl JinjaFormatter {
             match node.token.token_type {
                 TokenType::JinjaExpression => {
                     if let Some(multiline) =
-                        self.format_expression_multiline(&node.value, base_indent)
+                        self.format_expression_multiline(&node.value, base_level)
                     {
                         arena[idx].value = CompactString::from(multiline);
                     }
@@ -68,7 +69,7 @@ impl JinjaFormatter {
                 | TokenType::JinjaBlockEnd
                 | TokenType::JinjaBlockKeyword => {
                     if let Some(multiline) =
-                        self.format_statement_multiline(&node.value, base_indent)
+                        self.format_statement_multiline(&node.value, base_level)
                     {
                         arena[idx].value = CompactString::from(multiline);
                     }
@@ -149,7 +150,7 @@ impl JinjaFormatter {
                 .map(|line| line.trim())
                 .filter(|line| !line.is_empty())
                 .collect();
-            let indent = "    ";
+            let indent = self.indent.prefix(1);
             let mut result = format!("{} \n", open);
             for line in &cleaned_lines {
                 result.push_str(indent);
@@ -520,7 +521,7 @@ impl JinjaFormatter {
     ///     )
     /// }}
     /// ```
-    fn format_expression_multiline(&self, value: &str, base_indent: usize) -> Option<String> {
+    fn format_expression_multiline(&self, value: &str, base_level: usize) -> Option<String> {
         let trimmed = value.trim();
         if !trimmed.starts_with("{{") || !trimmed.ends_with("}}") {
             return None;
@@ -551,10 +552,10 @@ impl JinjaFormatter {
                         let list_content = &single_arg[1..single_arg.len() - 1];
                         let list_items = split_by_commas(list_content);
                         if list_items.len() > 1 {
-                            let indent1 = indent_str(base_indent + 4);
-                            let indent2 = indent_str(base_indent + 8);
-                            let indent3 = indent_str(base_indent + 12);
-                            let close_indent = indent_str(base_indent);
+                            let indent1 = self.indent.prefix(base_level + 1);
+                            let indent2 = self.indent.prefix(base_level + 2);
+                            let indent3 = self.indent.prefix(base_level + 3);
+                            let close_indent = self.indent.prefix(base_level);
 
                             let mut result = String::with_capacity(256);
                             result.push_str(open);
@@ -586,9 +587,9 @@ impl JinjaFormatter {
                     }
                 }
 
-                let indent1 = indent_str(base_indent + 4);
-                let indent2 = indent_str(base_indent + 8);
-                let close_indent = indent_str(base_indent);
+                let indent1 = self.indent.prefix(base_level + 1);
+                let indent2 = self.indent.prefix(base_level + 2);
+                let close_indent = self.indent.prefix(base_level);
 
                 let mut result = String::with_capacity(256);
                 result.push_str(open);
@@ -618,8 +619,8 @@ impl JinjaFormatter {
             }
         }
 
-        let indent1 = indent_str(base_indent + 4);
-        let close_indent = indent_str(base_indent);
+        let indent1 = self.indent.prefix(base_level + 1);
+        let close_indent = self.indent.prefix(base_level);
         Some(format!(
             "{}\n{}{}\n{}{}",
             open, indent1, inner, close_indent, close
@@ -636,7 +637,7 @@ impl JinjaFormatter {
     ///     arg2,
     /// ) %}
     /// ```
-    fn format_statement_multiline(&self, value: &str, base_indent: usize) -> Option<String> {
+    fn format_statement_multiline(&self, value: &str, base_level: usize) -> Option<String> {
         let trimmed = value.trim();
 
         let (open_delim, inner, close_delim) = if trimmed.starts_with("{%-") {
@@ -668,8 +669,8 @@ impl JinjaFormatter {
                 let after_close = inner[close_pos + 1..].trim();
                 let args = split_by_commas(args_content);
 
-                let indent1 = indent_str(base_indent + 4);
-                let close_indent = indent_str(base_indent);
+                let indent1 = self.indent.prefix(base_level + 1);
+                let close_indent = self.indent.prefix(base_level);
 
                 let mut result = String::with_capacity(256);
                 result.push_str(open_delim);
@@ -713,8 +714,8 @@ impl JinjaFormatter {
                 if items.len() <= 1 {
                     let tilde_parts = split_by_tilde(list_content);
                     if tilde_parts.len() > 1 {
-                        let indent1 = indent_str(base_indent + 4);
-                        let close_indent = indent_str(base_indent);
+                        let indent1 = self.indent.prefix(base_level + 1);
+                        let close_indent = self.indent.prefix(base_level);
                         let mut result = String::with_capacity(256);
                         result.push_str(open_delim);
                         result.push(' ');
@@ -738,8 +739,8 @@ impl JinjaFormatter {
                     return None;
                 }
 
-                let indent1 = indent_str(base_indent + 4);
-                let close_indent = indent_str(base_indent);
+                let indent1 = self.indent.prefix(base_level + 1);
+                let close_indent = self.indent.prefix(base_level);
 
                 let mut result = String::with_capacity(256);
                 result.push_str(open_delim);
@@ -765,8 +766,8 @@ impl JinjaFormatter {
         }
 
         if inner.len() + open_delim.len() + close_delim.len() + 4 > self.max_length {
-            let indent1 = indent_str(base_indent + 4);
-            let close_indent = indent_str(base_indent);
+            let indent1 = self.indent.prefix(base_level + 1);
+            let close_indent = self.indent.prefix(base_level);
             return Some(format!(
                 "{} \n{}{}\n{}{}",
                 open_delim, indent1, inner, close_indent, close_delim
@@ -982,28 +983,28 @@ mod tests {
 
     #[test]
     fn test_normalize_expression() {
-        let formatter = JinjaFormatter::new(88);
+        let formatter = JinjaFormatter::new(88, Indent::default());
         let result = formatter.normalize_expression("{{  my_var  }}");
         assert_eq!(result, Some("{{ my_var }}".to_string()));
     }
 
     #[test]
     fn test_normalize_statement() {
-        let formatter = JinjaFormatter::new(88);
+        let formatter = JinjaFormatter::new(88, Indent::default());
         let result = formatter.normalize_statement("{%  if  condition  %}");
         assert_eq!(result, Some("{% if condition %}".to_string()));
     }
 
     #[test]
     fn test_normalize_with_whitespace_control() {
-        let formatter = JinjaFormatter::new(88);
+        let formatter = JinjaFormatter::new(88, Indent::default());
         let result = formatter.normalize_statement("{%- if condition -%}");
         assert_eq!(result, Some("{%- if condition -%}".to_string()));
     }
 
     #[test]
     fn test_normalize_expression_with_dash() {
-        let formatter = JinjaFormatter::new(88);
+        let formatter = JinjaFormatter::new(88, Indent::default());
         let result = formatter.normalize_expression("{{- my_var -}}");
         assert_eq!(result, Some("{{- my_var -}}".to_string()));
     }
@@ -1023,7 +1024,7 @@ mod tests {
 
     #[test]
     fn test_multiline_expression() {
-        let formatter = JinjaFormatter::new(88);
+        let formatter = JinjaFormatter::new(88, Indent::default());
         let value = r#"{{ config(target_database="analytics", target_schema=target.schema + "_snapshots", unique_key="id", strategy="timestamp", updated_at="updated_at") }}"#;
         let result = formatter.format_expression_multiline(value, 4);
         assert!(result.is_some());
@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
 use memchr::{memchr, memchr2};
 
 use crate::action::Action;
@@ -15,6 +18,12 @@ pub enum LexState {
     Function,
     Warehouse,
     Clone,
+    Insert,
+    Update,
+    Merge,
+    CreateTable,
+    Block,
+    Stage,
 }
 
 /// Result of a single lex step: what action to take, how far to advance,
@@ -26,6 +35,189 @@ pub struct LexResult<'a> {
     pub token_text: &'a str,
 }
 
+/// A perfect-hash set of lowercased keywords.
+pub type KeywordSet = phf::Set<&'static str>;
+
+/// Keyword tables a dialect contributes on top of the keywords shared by
+/// every dialect. Words that appear in another dialect's tables but not in
+/// these are lexed as names.
+#[derive(Debug, Clone)]
+pub struct Keywords {
+    /// Clause keywords, e.g. ClickHouse `prewhere`.
+    pub unterm_keywords: &'static [&'static KeywordSet],
+    /// Word operators, e.g. ClickHouse `global in`.
+    pub word_operators: &'static [&'static KeywordSet],
+    /// Set operators, e.g. DuckDB `union by name`.
+    pub set_operators: &'static [&'static KeywordSet],
+    /// First words of statements sqlfmt passes through unformatted,
+    /// e.g. Snowflake `put`.
+    pub reserved_words: &'static [&'static KeywordSet],
+    /// Keywords a user-defined dialect adds to these tables.
+    pub custom: Option<Arc<CustomKeywords>>,
+}
+
+/// Keywords of a user-defined dialect, lowercased with single spaces
+/// between words.
+#[derive(Debug, Default)]
+pub struct CustomKeywords {
+    pub unterm_keywords: HashSet<String>,
+    pub word_operators: HashSet<String>,
+    pub set_operators: HashSet<String>,
+    pub reserved_words: HashSet<String>,
+}
+
+impl CustomKeywords {
+    /// Match the longest multi-word keyword that starts with `first_lower`.
+    /// Returns the bytes consumed after the first word.
+    fn try_phrase(&self, first_lower: &str, after_word: &[u8]) -> Option<usize> {
+        [
+            &self.unterm_keywords,
+            &self.word_operators,
+            &self.set_operators,
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|phrase| {
+            let rest = phrase.strip_prefix(first_lower)?.strip_prefix(' ')?;
+            try_sequence(after_word, &rest.split(' ').collect::<Vec<_>>())
+        })
+        .max()
+    }
+}
+
+impl Keywords {
+    pub fn is_unterm_keyword(&self, kw: &str) -> bool {
+        self.unterm_keywords.iter().any(|set| set.contains(kw))
+            || self
+                .custom
+                .as_ref()
+                .is_some_and(|c| c.unterm_keywords.contains(kw))
+    }
+
+    pub fn is_word_operator(&self, kw: &str) -> bool {
+        self.word_operators.iter().any(|set| set.contains(kw))
+            || self
+                .custom
+                .as_ref()
+                .is_some_and(|c| c.word_operators.contains(kw))
+    }
+
+    pub fn is_set_operator(&self, kw: &str) -> bool {
+        self.set_operators.iter().any(|set| set.contains(kw))
+            || self
+                .custom
+                .as_ref()
+                .is_some_and(|c| c.set_operators.contains(kw))
+    }
+
+    pub fn is_reserved_word(&self, first_word: &str) -> bool {
+        self.reserved_words
+            .iter()
+            .any(|set| set.contains(first_word))
+            || self
+                .custom
+                .as_ref()
+                .is_some_and(|c| c.reserved_words.contains(first_word))
+    }
+
+    /// Whether these tables give `kw` (or its first word) a meaning.
+    fn recognizes(&self, kw: &str, first_word: &str) -> bool {
+        self.is_unterm_keyword(kw)
+            || self.is_word_operator(kw)
+            || self.is_set_operator(kw)
+            || self.is_reserved_word(first_word)
+    }
+}
+
+/// Everything a dialect contributes to lexing: its keyword tables and the
+/// dialect-specific tokens it enables.
+#[derive(Debug, Clone)]
+pub struct Syntax {
+    pub keywords: Keywords,
+    /// Snowflake stage references: `@stage/path/`, `@~` and `@%table`.
+    pub stage_references: bool,
+    /// Snowflake `$` inside names (`metadata$filename`).
+    pub dollar_names: bool,
+    /// Snowflake `->>`, which pipes one statement's result into the next
+    /// (otherwise `->>` is the JSON extraction operator).
+    pub flow_operator: bool,
+    /// Snowflake `COPY INTO` and `CREATE STAGE / PIPE / STREAM / TASK`,
+    /// which are formatted with one option per line.
+    pub stage_statements: bool,
+    /// Postgres operator lexing: any run of operator characters is a single
+    /// operator (`~~*`, `>>=`, `#`), following the Postgres lexer's rules.
+    pub postgres_operators: bool,
+    /// SQL-standard strings: backslash is an ordinary character in `'...'`
+    /// and `"..."`, while `E'...'` escape strings and `U&'...'` Unicode
+    /// strings are lexed as single tokens.
+    pub standard_strings: bool,
+    /// BigQuery raw and bytes literals (`r'...'`, `b"..."`, `rb'''...'''`)
+    /// are lexed as single tokens.
+    pub string_prefixes: bool,
+    /// BigQuery `"..."` is a string literal rather than a quoted identifier.
+    pub double_quoted_strings: bool,
+    /// Databricks `${name}` widget and configuration references.
+    pub widget_references: bool,
+    /// T-SQL `[name]` quoted identifiers (otherwise `[` opens a bracket).
+    pub bracket_identifiers: bool,
+    /// T-SQL `@@rowcount` system functions (otherwise `@@` is the text
+    /// search operator).
+    pub system_variables: bool,
+    /// T-SQL `GO` on its own line ends a batch, like a semicolon.
+    pub batch_separator: bool,
+    /// Snowflake Scripting and BigQuery procedural blocks: `BEGIN ... END`,
+    /// `IF ... END IF` and loops at the start of a statement indent their body.
+    pub scripting_blocks: bool,
+    /// Snowflake Scripting `DECLARE` opens a declaration section closed by
+    /// `BEGIN` (otherwise `DECLARE` is a single statement).
+    pub declare_sections: bool,
+    /// T-SQL `TOP (n)`, `TOP n PERCENT` and `WITH TIES` (otherwise only
+    /// `select top <n>`).
+    pub select_top: bool,
+    /// T-SQL control flow: `IF` / `WHILE` / `ELSE` take a single statement
+    /// or a `BEGIN ... END` block, and `BEGIN TRAN ... COMMIT` indents.
+    pub tsql_blocks: bool,
+    /// `# comment` line comments (otherwise `#` is an operator, or part of a
+    /// name like T-SQL `#temp`).
+    pub hash_comments: bool,
+    /// MySQL `DELIMITER //` lines, which change the statement terminator.
+    pub delimiter_statements: bool,
+    /// MySQL `limit <offset>, <count>`, kept on one line like a single
+    /// keyword (otherwise the comma splits it like a list).
+    pub limit_offset_count: bool,
+    /// MySQL `:=`, which assigns a user variable and binds like `=`.
+    pub assignment_operator: bool,
+}
+
+impl Syntax {
+    /// The keyword tables with no dialect-specific tokens.
+    pub fn new(keywords: Keywords) -> Self {
+        Self {
+            keywords,
+            stage_references: false,
+            dollar_names: false,
+            flow_operator: false,
+            stage_statements: false,
+            postgres_operators: false,
+            standard_strings: false,
+            string_prefixes: false,
+            double_quoted_strings: false,
+            widget_references: false,
+            bracket_identifiers: false,
+            system_variables: false,
+            batch_separator: false,
+            select_top: false,
+            scripting_blocks: false,
+            declare_sections: false,
+            tsql_blocks: false,
+            hash_comments: false,
+            delimiter_statements: false,
+            limit_offset_count: false,
+            assignment_operator: false,
+        }
+    }
+}
+
 // ---- Static action constants ----
 // These replace the Box::leak'd actions from rules/mod.rs.
 
@@ -38,6 +230,19 @@ static A_RESERVED_NONRESERVED_UNTERM: Action = Action::HandleReservedKeyword {
     inner: &A_NONRESERVED_UNTERM,
 };
 
+static A_STATEMENT_UNTERM: Action = Action::HandleStatementStartKeyword {
+    statement_start: &A_UNTERM,
+    otherwise: &A_NAME,
+};
+static A_RESERVED_STATEMENT_UNTERM: Action = Action::HandleReservedKeyword {
+    inner: &A_STATEMENT_UNTERM,
+};
+
+static A_MATCH_RECOGNIZE_UNTERM: Action = Action::HandleMatchRecognizeKeyword { inner: &A_UNTERM };
+static A_RESERVED_MATCH_RECOGNIZE_UNTERM: Action = Action::HandleReservedKeyword {
+    inner: &A_MATCH_RECOGNIZE_UNTERM,
+};
+
 static A_NAME: Action = Action::AddNode {
     token_type: TokenType::Name,
 };
@@ -61,6 +266,29 @@ static A_ON: Action = Action::AddNode {
 };
 static A_RESERVED_ON: Action = Action::HandleReservedKeyword { inner: &A_ON };
 
+static A_DDL_KEYWORD: Action = Action::AddNode {
+    token_type: TokenType::DdlKeyword,
+};
+static A_RESERVED_DDL_KEYWORD: Action = Action::HandleReservedKeyword {
+    inner: &A_DDL_KEYWORD,
+};
+
+static A_DATA_TYPE: Action = Action::AddNode {
+    token_type: TokenType::DataType,
+};
+static A_RESERVED_DATA_TYPE: Action = Action::HandleReservedKeyword {
+    inner: &A_DATA_TYPE,
+};
+
+// COMMENT is a table option after the column list and a column option inside it
+static A_CREATE_TABLE_COMMENT: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_UNTERM,
+    nested: &A_DDL_KEYWORD,
+};
+static A_RESERVED_CREATE_TABLE_COMMENT: Action = Action::HandleReservedKeyword {
+    inner: &A_CREATE_TABLE_COMMENT,
+};
+
 static A_OPERATOR: Action = Action::AddNode {
     token_type: TokenType::Operator,
 };
@@ -109,6 +337,7 @@ static A_RESERVED_DATA: Action = Action::HandleReservedKeyword { inner: &A_DATA
 static A_NUMBER: Action = Action::HandleNumber;
 static A_NEWLINE: Action = Action::HandleNewline;
 static A_SEMICOLON: Action = Action::HandleSemicolon;
+static A_DELIMITER: Action = Action::HandleDelimiter;
 static A_COMMENT: Action = Action::AddComment;
 
 static A_ANGLE_CLOSE: Action = Action::HandleClosingAngleBracket;
@@ -125,6 +354,7 @@ static A_JINJA_STMT: Action = Action::HandleJinja {
 
 static A_SET_OP: Action = Action::HandleSetOperator;
 static A_RESERVED_SET_OP: Action = Action::HandleReservedKeyword { inner: &A_SET_OP };
+static A_FLOW_OP: Action = Action::HandleFlowOperator;
 
 static A_STATEMENT_START: Action = Action::AddNode {
     token_type: TokenType::StatementStart,
@@ -179,6 +409,16 @@ static A_RESERVED_NONRESERVED_WAREHOUSE: Action = Action::HandleReservedKeyword
     inner: &A_NONRESERVED_WAREHOUSE,
 };
 
+static A_LEX_CREATE_TABLE: Action = Action::LexRuleset {
+    ruleset_name: "create_table",
+};
+static A_NONRESERVED_CREATE_TABLE: Action = Action::HandleNonreservedTopLevelKeyword {
+    inner: &A_LEX_CREATE_TABLE,
+};
+static A_RESERVED_NONRESERVED_CREATE_TABLE: Action = Action::HandleReservedKeyword {
+    inner: &A_NONRESERVED_CREATE_TABLE,
+};
+
 static A_LEX_CLONE: Action = Action::LexRuleset {
     ruleset_name: "clone",
 };
@@ -189,6 +429,147 @@ static A_RESERVED_NONRESERVED_CLONE: Action = Action::HandleReservedKeyword {
     inner: &A_NONRESERVED_CLONE,
 };
 
+static A_LEX_INSERT: Action = Action::LexRuleset {
+    ruleset_name: "insert",
+};
+static A_NONRESERVED_INSERT: Action = Action::HandleNonreservedTopLevelKeyword {
+    inner: &A_LEX_INSERT,
+};
+static A_RESERVED_NONRESERVED_INSERT: Action = Action::HandleReservedKeyword {
+    inner: &A_NONRESERVED_INSERT,
+};
+
+static A_LEX_UPDATE: Action = Action::LexRuleset {
+    ruleset_name: "update",
+};
+static A_NONRESERVED_UPDATE: Action = Action::HandleNonreservedTopLevelKeyword {
+    inner: &A_LEX_UPDATE,
+};
+static A_RESERVED_NONRESERVED_UPDATE: Action = Action::HandleReservedKeyword {
+    inner: &A_NONRESERVED_UPDATE,
+};
+
+static A_RESERVED_LEX_UPDATE: Action = Action::HandleReservedKeyword {
+    inner: &A_LEX_UPDATE,
+};
+// VALUES in an UPDATE is a function, unless it starts a subquery's rows
+static A_UPDATE_VALUES: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_NAME,
+    nested: &A_UNTERM,
+};
+static A_RESERVED_UPDATE_VALUES: Action = Action::HandleReservedKeyword {
+    inner: &A_UPDATE_VALUES,
+};
+
+static A_LEX_MERGE: Action = Action::LexRuleset {
+    ruleset_name: "merge",
+};
+static A_NONRESERVED_MERGE: Action = Action::HandleNonreservedTopLevelKeyword {
+    inner: &A_LEX_MERGE,
+};
+static A_RESERVED_NONRESERVED_MERGE: Action = Action::HandleReservedKeyword {
+    inner: &A_NONRESERVED_MERGE,
+};
+
+// MERGE ... ON starts a clause; a join's ON inside the USING subquery does not
+static A_MERGE_ON: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_UNTERM,
+    nested: &A_ON,
+};
+static A_RESERVED_MERGE_ON: Action = Action::HandleReservedKeyword { inner: &A_MERGE_ON };
+
+static A_LEX_BLOCK: Action = Action::LexRuleset {
+    ruleset_name: "block",
+};
+// IF, LOOP, BEGIN, ... only start a procedural block at the start of a statement
+static A_BLOCK_STATEMENT: Action = Action::HandleStatementStartKeyword {
+    statement_start: &A_LEX_BLOCK,
+    otherwise: &A_NAME,
+};
+static A_RESERVED_BLOCK_STATEMENT: Action = Action::HandleReservedKeyword {
+    inner: &A_BLOCK_STATEMENT,
+};
+
+static A_BLOCK_START: Action = Action::HandleBlockStart;
+static A_STATEMENT_BLOCK_START: Action = Action::HandleStatementStartKeyword {
+    statement_start: &A_BLOCK_START,
+    otherwise: &A_NAME,
+};
+static A_RESERVED_STATEMENT_BLOCK_START: Action = Action::HandleReservedKeyword {
+    inner: &A_STATEMENT_BLOCK_START,
+};
+
+// T-SQL BEGIN starts a block wherever it appears, e.g. after an IF condition
+static A_RESERVED_LEX_BLOCK: Action = Action::HandleReservedKeyword {
+    inner: &A_LEX_BLOCK,
+};
+static A_RESERVED_BLOCK_START: Action = Action::HandleReservedKeyword {
+    inner: &A_BLOCK_START,
+};
+
+static A_BLOCK_KEYWORD: Action = Action::AddNode {
+    token_type: TokenType::BlockKeyword,
+};
+// LOOP starts a block on its own, and ends the header of FOR ... LOOP
+static A_BLOCK_LOOP: Action = Action::HandleStatementStartKeyword {
+    statement_start: &A_BLOCK_START,
+    otherwise: &A_BLOCK_KEYWORD,
+};
+static A_RESERVED_BLOCK_LOOP: Action = Action::HandleReservedKeyword {
+    inner: &A_BLOCK_LOOP,
+};
+// THEN / ELSE / WHEN inside brackets belong to a CASE expression
+static A_BLOCK_OR_CASE_KEYWORD: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_BLOCK_KEYWORD,
+    nested: &A_UNTERM,
+};
+static A_RESERVED_BLOCK_OR_CASE_KEYWORD: Action = Action::HandleReservedKeyword {
+    inner: &A_BLOCK_OR_CASE_KEYWORD,
+};
+static A_TOP_LEVEL_BLOCK_KEYWORD: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_BLOCK_KEYWORD,
+    nested: &A_NAME,
+};
+static A_RESERVED_TOP_LEVEL_BLOCK_KEYWORD: Action = Action::HandleReservedKeyword {
+    inner: &A_TOP_LEVEL_BLOCK_KEYWORD,
+};
+
+static A_BLOCK_END: Action = Action::HandleBlockEnd;
+static A_RESERVED_BLOCK_END: Action = Action::HandleReservedKeyword {
+    inner: &A_BLOCK_END,
+};
+static A_BLOCK_OR_CASE_END: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_BLOCK_END,
+    nested: &A_SAFE_STATEMENT_END,
+};
+static A_RESERVED_BLOCK_OR_CASE_END: Action = Action::HandleReservedKeyword {
+    inner: &A_BLOCK_OR_CASE_END,
+};
+
+static A_LEX_STAGE: Action = Action::LexRuleset {
+    ruleset_name: "stage",
+};
+static A_NONRESERVED_STAGE: Action = Action::HandleNonreservedTopLevelKeyword {
+    inner: &A_LEX_STAGE,
+};
+static A_RESERVED_NONRESERVED_STAGE: Action = Action::HandleReservedKeyword {
+    inner: &A_NONRESERVED_STAGE,
+};
+// COPY / stage options start a line; inside brackets they are names
+static A_STAGE_OPTION: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_UNTERM,
+    nested: &A_NAME,
+};
+static A_RESERVED_STAGE_OPTION: Action = Action::HandleReservedKeyword {
+    inner: &A_STAGE_OPTION,
+};
+// The AS of a pipe or task starts its SQL body; a nested AS is an alias
+static A_STAGE_AS: Action = Action::HandleTopLevelOrNestedKeyword {
+    top_level: &A_DDL_AS,
+    nested: &A_WORD_OP,
+};
+static A_RESERVED_STAGE_AS: Action = Action::HandleReservedKeyword { inner: &A_STAGE_AS };
+
 static A_LEX_UNSUPPORTED: Action = Action::LexRuleset {
     ruleset_name: "unsupported",
 };
@@ -245,6 +626,39 @@ fn scan_word(bytes: &[u8]) -> usize {
     i
 }
 
+/// Scan a Snowflake identifier, which may contain `$` after its first
+/// character (`metadata$filename`). Returns byte length of the identifier.
+fn scan_dollar_word(bytes: &[u8]) -> usize {
+    let mut i = scan_word(bytes);
+    while i > 0 && bytes.get(i) == Some(&b'$') {
+        i += 1 + scan_word(&bytes[i + 1..]);
+    }
+    i
+}
+
+/// Scan a Snowflake stage reference: `@` followed by a stage name (or `~`
+/// for the user stage, `%table` for a table stage) and an optional path,
+/// e.g. `@db.schema.stage/path/file.csv`. Returns 0 if there is no name.
+fn scan_stage_reference(bytes: &[u8]) -> usize {
+    let mut i = 1;
+    match bytes.get(i) {
+        Some(b'~') => i += 1,
+        Some(b'%') => i += 1 + scan_word(&bytes[2..]),
+        Some(b'"') => i += scan_string(&bytes[1..]),
+        _ => i += scan_word(&bytes[1..]),
+    }
+    if i == 1 || (i == 2 && bytes[1] == b'%') {
+        return 0;
+    }
+    while i < bytes.len()
+        && (bytes[i].is_ascii_alphanumeric()
+            || matches!(bytes[i], b'_' | b'$' | b'.' | b'/' | b'-' | b'=' | b'*'))
+    {
+        i += 1;
+    }
+    i
+}
+
 /// Scan a number starting at bytes[0]. Handles:
 /// - Hex (0x...), binary (0b...), octal (0o...)
 /// - Decimal with optional fractional part and scientific notation
@@ -323,12 +737,12 @@ fn scan_number(bytes: &[u8]) -> usize {
             i += 2;
         } else {
             match bytes[i] {
+                // Only consume if followed by word boundary
                 b'd' | b'D' | b'f' | b'F' | b'l' | b'L' | b's' | b'S' | b'k' | b'K' | b'y'
-                | b'Y' => {
-                    // Only consume if followed by word boundary
-                    if i + 1 >= len || !bytes[i + 1].is_ascii_alphanumeric() {
-                        i += 1;
-                    }
+                | b'Y'
+                    if i + 1 >= len || !bytes[i + 1].is_ascii_alphanumeric() =>
+                {
+                    i += 1;
                 }
                 _ => {}
             }
@@ -365,6 +779,91 @@ fn scan_string(bytes: &[u8]) -> usize {
     bytes.len()
 }
 
+/// Scan a SQL-standard string literal, in which a doubled quote is the only
+/// escape. Returns the byte length including delimiters.
+fn scan_standard_string(bytes: &[u8]) -> usize {
+    let quote = bytes[0];
+    let mut i = 1;
+    while let Some(offset) = memchr(quote, &bytes[i..]) {
+        let pos = i + offset;
+        if bytes.get(pos + 1) == Some(&quote) {
+            i = pos + 2;
+            continue;
+        }
+        return pos + 1;
+    }
+    bytes.len()
+}
+
+/// Scan a T-SQL `[name]` identifier, in which `]]` escapes a bracket.
+/// Returns the byte length including delimiters.
+fn scan_bracket_identifier(bytes: &[u8]) -> usize {
+    let mut i = 1;
+    while let Some(offset) = memchr(b']', &bytes[i..]) {
+        let pos = i + offset;
+        if bytes.get(pos + 1) == Some(&b']') {
+            i = pos + 2;
+            continue;
+        }
+        return pos + 1;
+    }
+    bytes.len()
+}
+
+/// Scan a T-SQL `GO` batch separator: `go` and an optional repeat count,
+/// alone on the rest of its line. Returns 0 if `bytes` is anything else.
+fn scan_batch_separator(bytes: &[u8]) -> usize {
+    if bytes.len() < 2 || !bytes[..2].eq_ignore_ascii_case(b"go") {
+        return 0;
+    }
+    let mut i = 2;
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t') {
+        i += 1;
+    }
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        i += 1;
+    }
+    let end = i;
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t' | b'\r') {
+        i += 1;
+    }
+    if i == bytes.len() || bytes[i] == b'\n' {
+        end
+    } else {
+        0
+    }
+}
+
+/// Scan a MySQL `DELIMITER //` line: the word and the new terminator,
+/// alone on the rest of its line. Returns 0 if `bytes` is anything else.
+fn scan_delimiter_statement(bytes: &[u8]) -> usize {
+    const WORD: &[u8] = b"delimiter";
+    if bytes.len() <= WORD.len() || !bytes[..WORD.len()].eq_ignore_ascii_case(WORD) {
+        return 0;
+    }
+    let mut i = WORD.len();
+    let ws_start = i;
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t') {
+        i += 1;
+    }
+    let start = i;
+    if start == ws_start {
+        return 0;
+    }
+    while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    let end = i;
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t' | b'\r') {
+        i += 1;
+    }
+    if end > start && (i == bytes.len() || bytes[i] == b'\n') {
+        end
+    } else {
+        0
+    }
+}
+
 /// Scan a triple-quoted string (''' or """). Returns byte length including delimiters.
 fn scan_triple_string(bytes: &[u8], quote: u8) -> usize {
     // bytes starts at the first quote char; first 3 are the opening delimiter
@@ -378,6 +877,26 @@ fn scan_triple_string(bytes: &[u8], quote: u8) -> usize {
     bytes.len()
 }
 
+/// Scan a BigQuery raw or bytes literal: `r`, `b`, `rb` or `br` (in any
+/// case) followed by a quoted or triple-quoted string. Returns 0 if `bytes`
+/// does not start with one.
+fn scan_prefixed_literal(bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < 2 && i < bytes.len() && matches!(bytes[i], b'r' | b'R' | b'b' | b'B') {
+        i += 1;
+    }
+    if i == 2 && bytes[0].eq_ignore_ascii_case(&bytes[1]) {
+        return 0;
+    }
+    match bytes.get(i) {
+        Some(&q @ (b'\'' | b'"')) if bytes[i..].starts_with(&[q, q, q]) => {
+            i + scan_triple_string(&bytes[i..], q)
+        }
+        Some(b'\'' | b'"') => i + scan_string(&bytes[i..]),
+        _ => 0,
+    }
+}
+
 /// Scan a line comment (-- or // or #). Returns byte length including prefix marker.
 fn scan_line_comment(bytes: &[u8]) -> usize {
     if let Some(offset) = memchr(b'\n', bytes) {
@@ -621,6 +1140,11 @@ fn try_multi_word(first_lower: &str, after_word: &[u8], state: LexState) -> Opti
         LexState::Grant => try_multi_word_grant(first_lower, after_word),
         LexState::Function => try_multi_word_function(first_lower, after_word),
         LexState::Warehouse => try_multi_word_warehouse(first_lower, after_word),
+        LexState::Insert => try_multi_word_insert(first_lower, after_word),
+        LexState::Update => try_multi_word_update(first_lower, after_word),
+        LexState::Merge => try_multi_word_merge(first_lower, after_word),
+        LexState::CreateTable => try_multi_word_create_table(first_lower, after_word),
+        LexState::Block => try_multi_word_block(first_lower, after_word),
         _ => None,
     };
 
@@ -636,6 +1160,19 @@ fn try_multi_word(first_lower: &str, after_word: &[u8], state: LexState) -> Opti
     }
 }
 
+/// Multi-word patterns of dialect-specific syntax.
+fn try_multi_word_syntax(first_lower: &str, after_word: &[u8], syntax: &Syntax) -> Option<usize> {
+    match first_lower {
+        "select" if syntax.select_top => try_select_top(after_word, true),
+        // T-SQL transactions
+        "begin" | "commit" | "rollback" if syntax.tsql_blocks => {
+            try_sequence_any(after_word, &[&["transaction"], &["tran"]])
+        }
+        "limit" if syntax.limit_offset_count => try_limit_offset_count(after_word),
+        _ => None,
+    }
+}
+
 /// State-independent multi-word patterns.
 fn try_multi_word_base(first_lower: &str, after_word: &[u8]) -> Option<usize> {
     match first_lower {
@@ -652,6 +1189,8 @@ fn try_multi_word_base(first_lower: &str, after_word: &[u8]) -> Option<usize> {
                 &["full", "join"],
                 &["any", "join"],
                 &["join"],
+                &["not", "in"],
+                &["in"],
             ],
         ),
         "any" => try_sequence_any(
@@ -713,19 +1252,27 @@ fn try_multi_word_base(first_lower: &str, after_word: &[u8]) -> Option<usize> {
                 &["into"], // select into
                 &["all"],
                 &["distinct"],
+                &["straight_join"],
                 // "select top N" handled specially below
             ],
         )
-        .or_else(|| try_select_top(after_word)),
+        .or_else(|| try_select_top(after_word, false)),
 
         // WITH
-        "with" => try_sequence(after_word, &["recursive"]),
+        "with" => try_sequence_any(
+            after_word,
+            &[&["recursive"], &["time", "zone"], &["ordinality"]],
+        ),
+        "without" => try_sequence(after_word, &["time", "zone"]),
 
         // DELETE FROM
         "delete" => try_sequence(after_word, &["from"]),
 
+        // EXECUTE IMMEDIATE
+        "execute" => try_sequence(after_word, &["immediate"]),
+
         // Standard multi-word clauses
-        "group" | "order" | "cluster" | "distribute" | "sort" | "partition" => {
+        "group" | "order" | "cluster" | "distribute" | "sort" | "partition" | "zorder" => {
             try_sequence(after_word, &["by"])
         }
         "lateral" => try_sequence_any(after_word, &[&["view", "outer"], &["view"], &["join"]]),
@@ -817,12 +1364,39 @@ fn try_multi_word_base(first_lower: &str, after_word: &[u8]) -> Option<usize> {
         // DDL multi-word
         "create" => try_create_extension(after_word),
         "alter" => try_alter_extension(after_word),
-        "drop" => try_sequence_any(after_word, &[&["function", "if", "exists"], &["function"]]),
+        "drop" => try_sequence_any(
+            after_word,
+            &[
+                &["function", "if", "exists"],
+                &["function"],
+                &["procedure", "if", "exists"],
+                &["procedure"],
+            ],
+        ),
         "insert" => try_sequence_any(
             after_word,
-            &[&["overwrite", "into"], &["overwrite"], &["into"]],
+            &[
+                &["overwrite", "table"],
+                &["overwrite", "into"],
+                &["overwrite", "all"],
+                &["overwrite", "first"],
+                &["overwrite"],
+                &["into", "table"],
+                &["into"],
+                &["all"],
+                &["first"],
+            ],
+        ),
+        "merge" => try_sequence_any(
+            after_word,
+            &[&["with", "schema", "evolution", "into"], &["into"]],
         ),
-        "merge" => try_sequence(after_word, &["into"]),
+        "copy" => try_sequence(after_word, &["into"]),
+
+        // MATCH_RECOGNIZE
+        "one" => try_sequence(after_word, &["row", "per", "match"]),
+        "all" => try_sequence(after_word, &["rows", "per", "match"]),
+        "after" => try_sequence(after_word, &["match", "skip"]),
         "rename" => try_sequence(after_word, &["table"]),
         "cache" => try_sequence(after_word, &["table"]),
         "clear" => try_sequence(after_word, &["cache"]),
@@ -871,8 +1445,20 @@ fn try_create_extension(bytes: &[u8]) -> Option<usize> {
     }
 }
 
+/// Length of a "function" or "procedure" keyword at the start of `bytes`, if any.
+fn routine_keyword_len(bytes: &[u8]) -> Option<usize> {
+    ["function", "procedure"].iter().find_map(|kw| {
+        let len = kw.len();
+        (bytes.len() >= len
+            && bytes[..len].eq_ignore_ascii_case(kw.as_bytes())
+            && (bytes.len() == len || !(bytes[len].is_ascii_alphanumeric() || bytes[len] == b'_')))
+            .then_some(len)
+    })
+}
+
 /// Scan past optional function modifiers (temporary, temp, secure, external, table)
-/// to find "function" keyword. Returns extra bytes consumed from start on success.
+/// to find the "function" (or "procedure") keyword. Returns extra bytes consumed
+/// from start on success.
 fn scan_past_modifiers_to_function(bytes: &[u8]) -> Option<usize> {
     let modifiers: &[&str] = &["temporary", "temp", "secure", "external", "table"];
     let mut pos = 0;
@@ -887,13 +1473,9 @@ fn scan_past_modifiers_to_function(bytes: &[u8]) -> Option<usize> {
             return None;
         }
 
-        // Check for "function"
-        if ws + 8 <= bytes.len()
-            && bytes[ws..ws + 8].eq_ignore_ascii_case(b"function")
-            && (ws + 8 >= bytes.len()
-                || !(bytes[ws + 8].is_ascii_alphanumeric() || bytes[ws + 8] == b'_'))
-        {
-            pos = ws + 8;
+        // Check for "function" / "procedure"
+        if let Some(len) = routine_keyword_len(&bytes[ws..]) {
+            pos = ws + len;
             // Optional "if not exists"
             if let Some(ine) = try_sequence(&bytes[pos..], &["if", "not", "exists"]) {
                 return Some(pos + ine);
@@ -924,8 +1506,8 @@ fn scan_past_modifiers_to_function(bytes: &[u8]) -> Option<usize> {
 
 /// Extend "alter" with DDL-specific patterns.
 fn try_alter_extension(bytes: &[u8]) -> Option<usize> {
-    // Try "function [if exists]"
-    if let Some(extra) = try_sequence(bytes, &["function"]) {
+    // Try "function|procedure [if exists]"
+    if let Some(extra) = try_sequence_any(bytes, &[&["function"], &["procedure"]]) {
         if let Some(ie) = try_sequence(&bytes[extra..], &["if", "exists"]) {
             return Some(extra + ie);
         }
@@ -964,6 +1546,14 @@ fn try_multi_word_function(first_lower: &str, after_word: &[u8]) -> Option<usize
         "not" => try_sequence(after_word, &["leakproof"]),
         "parallel" => try_sequence_any(after_word, &[&["safe"], &["unsafe"], &["restricted"]]),
         "security" => try_sequence_any(after_word, &[&["definer"], &["invoker"]]),
+        "execute" => try_sequence_any(
+            after_word,
+            &[
+                &["as", "restricted", "caller"],
+                &["as", "caller"],
+                &["as", "owner"],
+            ],
+        ),
         "set" => try_sequence_any(
             after_word,
             &[
@@ -1015,95 +1605,267 @@ fn try_multi_word_warehouse(first_lower: &str, after_word: &[u8]) -> Option<usiz
     }
 }
 
-/// Scan ahead in remaining text to detect a clone pattern:
-/// (database|schema|table|stage|file format|sequence|stream|task) [if not exists] <name> clone
-fn scan_rest_for_clone(rest: &[u8]) -> bool {
-    let mut pos = 0;
-
-    // Skip whitespace
-    while pos < rest.len() && rest[pos].is_ascii_whitespace() {
-        pos += 1;
+/// Insert-state multi-word patterns.
+fn try_multi_word_insert(first_lower: &str, after_word: &[u8]) -> Option<usize> {
+    match first_lower {
+        "on" => try_sequence_any(
+            after_word,
+            &[&["conflict"], &["duplicate", "key", "update"]],
+        ),
+        "do" => try_sequence_any(after_word, &[&["update", "set"], &["nothing"]]),
+        "default" => try_sequence(after_word, &["values"]),
+        "overriding" => try_sequence_any(after_word, &[&["system", "value"], &["user", "value"]]),
+        _ => None,
     }
-    if pos >= rest.len() {
-        return false;
+}
+
+/// Update-state multi-word patterns.
+fn try_multi_word_update(first_lower: &str, after_word: &[u8]) -> Option<usize> {
+    match first_lower {
+        "on" => try_sequence(after_word, &["duplicate", "key", "update"]),
+        _ => None,
     }
+}
 
-    // Object type: database|schema|table|stage|sequence|stream|task
-    let object_types: &[&str] = &[
-        "database", "schema", "table", "stage", "sequence", "stream", "task",
-    ];
-    let mut found_type = false;
+/// Merge-state multi-word patterns.
+fn try_multi_word_merge(first_lower: &str, after_word: &[u8]) -> Option<usize> {
+    match first_lower {
+        "when" => try_sequence_any(
+            after_word,
+            &[
+                &["not", "matched", "by", "source"],
+                &["not", "matched", "by", "target"],
+                &["not", "matched"],
+                &["matched"],
+            ],
+        ),
+        "then" => try_sequence_any(
+            after_word,
+            &[
+                &["update", "set"],
+                &["insert", "by", "name"],
+                &["insert"],
+                &["delete"],
+                &["do", "nothing"],
+            ],
+        ),
+        _ => None,
+    }
+}
 
-    // Check for "file format" (two words)
-    if let Some(extra) = try_sequence(&rest[pos..], &["file", "format"]) {
-        pos += extra;
-        found_type = true;
+/// Block-state multi-word patterns: the END of each kind of block, and
+/// BigQuery's RAISE USING MESSAGE.
+fn try_multi_word_block(first_lower: &str, after_word: &[u8]) -> Option<usize> {
+    match first_lower {
+        "end" => try_sequence_any(
+            after_word,
+            &[&["if"], &["loop"], &["while"], &["for"], &["repeat"]],
+        ),
+        "raise" => try_sequence(after_word, &["using", "message"]),
+        _ => None,
     }
+}
 
-    if !found_type {
-        for &obj_type in object_types {
-            let olen = obj_type.len();
-            if pos + olen <= rest.len()
-                && rest[pos..pos + olen].eq_ignore_ascii_case(obj_type.as_bytes())
-                && (pos + olen >= rest.len()
-                    || !(rest[pos + olen].is_ascii_alphanumeric() || rest[pos + olen] == b'_'))
-            {
-                pos += olen;
-                found_type = true;
-                break;
+/// Create-table-state multi-word patterns: the statement keyword and the
+/// multi-word parts of column definitions and table constraints.
+fn try_multi_word_create_table(first_lower: &str, after_word: &[u8]) -> Option<usize> {
+    match first_lower {
+        "create" => {
+            let mut pos = try_sequence(after_word, &["or", "replace"]).unwrap_or(0);
+            pos += try_table_modifiers(&after_word[pos..]).unwrap_or(0);
+            pos += try_sequence(&after_word[pos..], &["table"])?;
+            if let Some(ine) = try_sequence(&after_word[pos..], &["if", "not", "exists"]) {
+                pos += ine;
             }
+            Some(pos)
         }
+        "not" => try_sequence_any(after_word, &[&["null"], &["deferrable"]]),
+        "primary" | "foreign" => try_sequence(after_word, &["key"]),
+        "partitioned" | "clustered" => try_sequence(after_word, &["by"]),
+        "generated" => try_sequence_any(
+            after_word,
+            &[
+                &["always", "as", "identity"],
+                &["by", "default", "as", "identity"],
+                &["always", "as"],
+            ],
+        ),
+        "on" => try_sequence_any(
+            after_word,
+            &[
+                &["commit", "preserve", "rows"],
+                &["commit", "delete", "rows"],
+                &["commit", "drop"],
+                &["delete"],
+                &["update"],
+            ],
+        ),
+        "set" => try_sequence_any(after_word, &[&["null"], &["default"]]),
+        "no" => try_sequence(after_word, &["action"]),
+        "with" | "without" => try_sequence(after_word, &["time", "zone"]),
+        "initially" => try_sequence_any(after_word, &[&["deferred"], &["immediate"]]),
+        _ => None,
     }
+}
 
-    if !found_type {
-        return false;
-    }
-
-    // Optional: "if not exists"
-    if let Some(extra) = try_sequence(&rest[pos..], &["if", "not", "exists"]) {
-        pos += extra;
-    }
+/// Table modifiers between `create [or replace]` and `table`.
+fn try_table_modifiers(bytes: &[u8]) -> Option<usize> {
+    try_sequence_any(
+        bytes,
+        &[
+            &["global", "temporary"],
+            &["global", "temp"],
+            &["local", "temporary"],
+            &["local", "temp"],
+            &["temporary"],
+            &["temp"],
+            &["transient"],
+            &["volatile"],
+            &["unlogged"],
+        ],
+    )
+}
 
-    // Object name: any word (skip whitespace first)
+/// Skip a (possibly qualified, possibly quoted) object name after optional
+/// whitespace. Returns bytes consumed, or None if no name was found.
+fn skip_object_name(rest: &[u8]) -> Option<usize> {
+    let mut pos = 0;
     while pos < rest.len() && rest[pos].is_ascii_whitespace() {
         pos += 1;
     }
     if pos >= rest.len() {
-        return false;
+        return None;
     }
 
-    // The name could be a quoted identifier or a regular word
-    if rest[pos] == b'"' || rest[pos] == b'`' || rest[pos] == b'\'' {
-        let quote = rest[pos];
-        pos += 1;
-        while pos < rest.len() && rest[pos] != quote {
+    loop {
+        if rest[pos] == b'"' || rest[pos] == b'`' || rest[pos] == b'\'' {
+            let quote = rest[pos];
             pos += 1;
-        }
-        if pos < rest.len() {
+            while pos < rest.len() && rest[pos] != quote {
+                pos += 1;
+            }
+            if pos >= rest.len() {
+                return None;
+            }
             pos += 1; // skip closing quote
+        } else if rest[pos..].starts_with(b"${") {
+            // Databricks widget reference
+            pos += memchr(b'}', &rest[pos..])? + 1;
         } else {
-            return false;
+            let start = pos;
+            while pos < rest.len() && (rest[pos].is_ascii_alphanumeric() || rest[pos] == b'_') {
+                pos += 1;
+            }
+            if pos == start {
+                return None;
+            }
         }
-    } else {
-        let start = pos;
-        while pos < rest.len()
-            && (rest[pos].is_ascii_alphanumeric() || rest[pos] == b'_' || rest[pos] == b'.')
-        {
+        if pos + 1 < rest.len() && rest[pos] == b'.' {
             pos += 1;
+        } else {
+            return Some(pos);
         }
-        if pos == start {
-            return false;
+    }
+}
+
+/// Scan ahead in remaining text to detect a table with a column list or
+/// Spark table options:
+/// [modifiers] table [if not exists] <name> ( | using | partitioned by | ...
+fn scan_rest_for_create_table(rest: &[u8]) -> bool {
+    let mut pos = try_table_modifiers(rest).unwrap_or(0);
+    let Some(extra) = try_sequence(&rest[pos..], &["table"]) else {
+        return false;
+    };
+    pos += extra;
+    if let Some(extra) = try_sequence(&rest[pos..], &["if", "not", "exists"]) {
+        pos += extra;
+    }
+    let Some(extra) = skip_object_name(&rest[pos..]) else {
+        return false;
+    };
+    pos += extra;
+    while pos < rest.len() && rest[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos < rest.len()
+        && (rest[pos] == b'('
+            || try_sequence_any(
+                &rest[pos..],
+                &[
+                    &["using"],
+                    &["partitioned", "by"],
+                    &["clustered", "by"],
+                    &["location"],
+                    &["options"],
+                    &["tblproperties"],
+                ],
+            )
+            .is_some())
+}
+
+/// Scan ahead in remaining text to detect a clone pattern:
+/// (database|schema|table|stage|file format|sequence|stream|task) [if not exists] <name> clone
+fn scan_rest_for_clone(rest: &[u8]) -> bool {
+    let mut pos = 0;
+
+    // Skip whitespace
+    while pos < rest.len() && rest[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    if pos >= rest.len() {
+        return false;
+    }
+
+    // Object type: database|schema|table|stage|sequence|stream|task
+    let object_types: &[&str] = &[
+        "database", "schema", "table", "stage", "sequence", "stream", "task",
+    ];
+    let mut found_type = false;
+
+    // Check for "file format" (two words)
+    if let Some(extra) = try_sequence(&rest[pos..], &["file", "format"]) {
+        pos += extra;
+        found_type = true;
+    }
+
+    if !found_type {
+        for &obj_type in object_types {
+            let olen = obj_type.len();
+            if pos + olen <= rest.len()
+                && rest[pos..pos + olen].eq_ignore_ascii_case(obj_type.as_bytes())
+                && (pos + olen >= rest.len()
+                    || !(rest[pos + olen].is_ascii_alphanumeric() || rest[pos + olen] == b'_'))
+            {
+                pos += olen;
+                found_type = true;
+                break;
+            }
         }
     }
 
+    if !found_type {
+        return false;
+    }
+
+    // Optional: "if not exists"
+    if let Some(extra) = try_sequence(&rest[pos..], &["if", "not", "exists"]) {
+        pos += extra;
+    }
+
+    // Object name: a regular word or a quoted identifier
+    let Some(extra) = skip_object_name(&rest[pos..]) else {
+        return false;
+    };
+    pos += extra;
+
     // Check for "clone"
     try_sequence(&rest[pos..], &["clone"]).is_some()
 }
 
-/// Scan ahead for "function" keyword (past optional DDL modifiers).
+/// Scan ahead for "function" or "procedure" keyword (past optional DDL modifiers).
 /// Used as a fallback when multi-word matching didn't capture the DDL object type.
 fn scan_rest_for_function(rest: &[u8]) -> bool {
-    // Scan past optional modifiers to find "function"
+    // Scan past optional modifiers to find "function" / "procedure"
     let modifiers: &[&str] = &["temporary", "temp", "secure", "external", "table"];
     let mut pos = 0;
 
@@ -1114,12 +1876,8 @@ fn scan_rest_for_function(rest: &[u8]) -> bool {
         if pos >= rest.len() {
             return false;
         }
-        // Check for "function"
-        if pos + 8 <= rest.len()
-            && rest[pos..pos + 8].eq_ignore_ascii_case(b"function")
-            && (pos + 8 >= rest.len()
-                || !(rest[pos + 8].is_ascii_alphanumeric() || rest[pos + 8] == b'_'))
-        {
+        // Check for "function" / "procedure"
+        if routine_keyword_len(&rest[pos..]).is_some() {
             return true;
         }
         // Check if current word is a modifier
@@ -1148,6 +1906,23 @@ fn scan_rest_for_warehouse(rest: &[u8]) -> bool {
     try_sequence(rest, &["warehouse"]).is_some()
 }
 
+/// Check if the rest of a CREATE statement names a Snowflake stage, pipe,
+/// stream or task.
+fn scan_rest_for_stage_object(rest: &[u8]) -> bool {
+    try_sequence_any(
+        rest,
+        &[
+            &["stage"],
+            &["temporary", "stage"],
+            &["temp", "stage"],
+            &["pipe"],
+            &["stream"],
+            &["task"],
+        ],
+    )
+    .is_some()
+}
+
 /// Try to match a sequence of words after the current position.
 /// Skips all whitespace including newlines between words (matching regex `\s+` behavior).
 /// Returns extra bytes consumed on success.
@@ -1185,8 +1960,9 @@ fn try_sequence_any(bytes: &[u8], sequences: &[&[&str]]) -> Option<usize> {
     None
 }
 
-/// Try "select top N" pattern
-fn try_select_top(bytes: &[u8]) -> Option<usize> {
+/// Try "select top N" pattern. With `tsql`, also T-SQL's `top (n)`,
+/// `percent` and `with ties`.
+fn try_select_top(bytes: &[u8], tsql: bool) -> Option<usize> {
     // Skip whitespace (including newlines)
     let mut pos = 0;
     while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
@@ -1204,12 +1980,52 @@ fn try_select_top(bytes: &[u8]) -> Option<usize> {
     while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
         pos += 1;
     }
-    // Match digits
-    let digit_start = pos;
-    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
-        pos += 1;
+    // Match digits, or a parenthesized expression: TOP (10), TOP (@n)
+    if tsql && pos < bytes.len() && bytes[pos] == b'(' {
+        let close = memchr(b')', &bytes[pos..])?;
+        if bytes[pos..pos + close].contains(&b'\n') {
+            return None;
+        }
+        pos += close + 1;
+    } else {
+        let digit_start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        if pos == digit_start {
+            return None;
+        }
+    }
+    if tsql {
+        pos += try_sequence(&bytes[pos..], &["percent"]).unwrap_or(0);
+        pos += try_sequence(&bytes[pos..], &["with", "ties"]).unwrap_or(0);
+    }
+    Some(pos)
+}
+
+/// Try the MySQL "limit <offset>, <count>" pattern, which stays on one line
+/// rather than being split like a list
+fn try_limit_offset_count(bytes: &[u8]) -> Option<usize> {
+    let skip_spaces = |mut pos: usize| {
+        while pos < bytes.len() && matches!(bytes[pos], b' ' | b'\t') {
+            pos += 1;
+        }
+        pos
+    };
+    let skip_digits = |mut pos: usize| {
+        let start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        (pos > start).then_some(pos)
+    };
+    let pos = skip_digits(skip_spaces(0))?;
+    let pos = skip_spaces(pos);
+    if bytes.get(pos) != Some(&b',') {
+        return None;
     }
-    if pos == digit_start {
+    let pos = skip_digits(skip_spaces(pos + 1))?;
+    if pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
         return None;
     }
     Some(pos)
@@ -1224,17 +2040,11 @@ fn scan_compound_operator(bytes: &[u8]) -> usize {
     }
     let b0 = bytes[0];
     match b0 {
-        b'>' => {
-            if len >= 2 {
-                match bytes[1] {
-                    b'=' => 2, // >=
-                    b'>' => 2, // >>
-                    _ => 0,
-                }
-            } else {
-                0
-            }
-        }
+        b'>' if len >= 2 => match bytes[1] {
+            b'=' => 2, // >=
+            b'>' => 2, // >>
+            _ => 0,
+        },
         b'<' => {
             if len >= 3 && bytes[1] == b'=' && bytes[2] == b'>' {
                 return 3; // <=>
@@ -1257,17 +2067,11 @@ fn scan_compound_operator(bytes: &[u8]) -> usize {
                 0
             }
         }
-        b'=' => {
-            if len >= 2 {
-                match bytes[1] {
-                    b'>' => 2, // =>
-                    b'=' => 2, // ==
-                    _ => 0,
-                }
-            } else {
-                0
-            }
-        }
+        b'=' if len >= 2 => match bytes[1] {
+            b'>' => 2, // =>
+            b'=' => 2, // ==
+            _ => 0,
+        },
         b'!' => {
             if len >= 3 && bytes[1] == b'!' && bytes[2] == b'=' {
                 return 3; // !!=
@@ -1344,17 +2148,11 @@ fn scan_compound_operator(bytes: &[u8]) -> usize {
             }
             0
         }
-        b'?' => {
-            if len >= 2 {
-                match bytes[1] {
-                    b'|' => 2, // ?|
-                    b'&' => 2, // ?&
-                    _ => 0,
-                }
-            } else {
-                0
-            }
-        }
+        b'?' if len >= 2 => match bytes[1] {
+            b'|' => 2, // ?|
+            b'&' => 2, // ?&
+            _ => 0,
+        },
         b'#' => {
             if len >= 3 && bytes[1] == b'>' && bytes[2] == b'>' {
                 return 3; // #>>
@@ -1379,11 +2177,57 @@ fn scan_compound_operator(bytes: &[u8]) -> usize {
     }
 }
 
+/// Scan a Postgres operator: the longest run of operator characters, cut
+/// short where a comment starts. A trailing `+` or `-` is dropped unless the
+/// run contains one of `~ ! @ # % ^ & | ?`, so `=-1` lexes as `=` then `-1`.
+/// Returns byte length, or 0 if `bytes` does not start with an operator.
+fn scan_postgres_operator(bytes: &[u8]) -> usize {
+    let mut len = 0;
+    while len < bytes.len()
+        && matches!(
+            bytes[len],
+            b'+' | b'-'
+                | b'*'
+                | b'/'
+                | b'<'
+                | b'>'
+                | b'='
+                | b'~'
+                | b'!'
+                | b'@'
+                | b'#'
+                | b'%'
+                | b'^'
+                | b'&'
+                | b'|'
+                | b'?'
+        )
+    {
+        if len > 0 && matches!(&bytes[len - 1..=len], b"--" | b"/*") {
+            len -= 1;
+            break;
+        }
+        len += 1;
+    }
+    let has_special = bytes[..len].iter().any(|b| {
+        matches!(
+            b,
+            b'~' | b'!' | b'@' | b'#' | b'%' | b'^' | b'&' | b'|' | b'?'
+        )
+    });
+    if !has_special {
+        while len > 1 && matches!(bytes[len - 1], b'+' | b'-') {
+            len -= 1;
+        }
+    }
+    len
+}
+
 // ---- Main dispatch ----
 
 /// Lex one token from `remaining` (source[pos..]) using byte dispatch.
 /// Returns a LexResult with the action, match_len, prefix, and token_text.
-pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>> {
+pub fn lex_one<'a>(remaining: &'a str, state: LexState, syntax: &Syntax) -> Option<LexResult<'a>> {
     let bytes = remaining.as_bytes();
     if bytes.is_empty() {
         return None;
@@ -1408,6 +2252,7 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                 prefix_len,
                 after_prefix,
                 after_prefix_str,
+                syntax,
             );
         }
         LexState::JinjaSetBlock => {
@@ -1435,6 +2280,7 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                 prefix_len,
                 after_prefix,
                 after_prefix_str,
+                syntax,
             );
         }
         _ => {}
@@ -1443,6 +2289,56 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
     // 3. Dispatch on first byte after prefix
     let b0 = after_prefix[0];
 
+    // Postgres operators of more than one character, and `#` (XOR rather
+    // than a comment). Single characters keep their usual handling below.
+    if syntax.postgres_operators {
+        let op_len = scan_postgres_operator(after_prefix);
+        if op_len > 1 || (op_len == 1 && b0 == b'#') {
+            return Some(LexResult {
+                action: &A_OPERATOR,
+                match_len: prefix_len + op_len,
+                prefix,
+                token_text: &after_prefix_str[..op_len],
+            });
+        }
+    }
+
+    if syntax.delimiter_statements && matches!(b0, b'd' | b'D') {
+        let len = scan_delimiter_statement(after_prefix);
+        if len > 0 {
+            return Some(LexResult {
+                action: &A_DELIMITER,
+                match_len: prefix_len + len,
+                prefix,
+                token_text: &after_prefix_str[..len],
+            });
+        }
+    }
+
+    if syntax.batch_separator && matches!(b0, b'g' | b'G') {
+        let go_len = scan_batch_separator(after_prefix);
+        if go_len > 0 {
+            return Some(LexResult {
+                action: &A_SEMICOLON,
+                match_len: prefix_len + go_len,
+                prefix,
+                token_text: &after_prefix_str[..go_len],
+            });
+        }
+    }
+
+    if syntax.string_prefixes && matches!(b0, b'r' | b'R' | b'b' | b'B') {
+        let literal_len = scan_prefixed_literal(after_prefix);
+        if literal_len > 0 {
+            return Some(LexResult {
+                action: &A_NAME,
+                match_len: prefix_len + literal_len,
+                prefix,
+                token_text: &after_prefix_str[..literal_len],
+            });
+        }
+    }
+
     match b0 {
         b'\n' => {
             let match_len = prefix_len + 1;
@@ -1476,6 +2372,13 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                     prefix,
                     token_text,
                 })
+            } else if syntax.flow_operator && after_prefix.starts_with(b"->>") {
+                Some(LexResult {
+                    action: &A_FLOW_OP,
+                    match_len: prefix_len + 3,
+                    prefix,
+                    token_text: &after_prefix_str[..3],
+                })
             } else {
                 // Operator: check compound operators first
                 let comp = scan_compound_operator(after_prefix);
@@ -1550,6 +2453,23 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                     token_text: &after_prefix_str[..comp],
                 });
             }
+            if !syntax.hash_comments {
+                // T-SQL `#temp` / `##global` tables, DuckDB `#1` columns
+                let hashes = if after_prefix.get(1) == Some(&b'#') {
+                    2
+                } else {
+                    1
+                };
+                let len = hashes + scan_word(&after_prefix[hashes..]);
+                let action = if len > hashes { &A_NAME } else { &A_OPERATOR };
+                let len = if len > hashes { len } else { 1 };
+                return Some(LexResult {
+                    action,
+                    match_len: prefix_len + len,
+                    prefix,
+                    token_text: &after_prefix_str[..len],
+                });
+            }
             // Line comment
             let comment_len = scan_line_comment(after_prefix);
             let token_text = &after_prefix_str[..comment_len];
@@ -1572,6 +2492,15 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
 
         // Single-quoted string
         b'\'' => {
+            if syntax.standard_strings {
+                let len = scan_standard_string(after_prefix);
+                return Some(LexResult {
+                    action: &A_NAME,
+                    match_len: prefix_len + len,
+                    prefix,
+                    token_text: &after_prefix_str[..len],
+                });
+            }
             // Check for triple quote '''
             if after_prefix.len() >= 3 && after_prefix[1] == b'\'' && after_prefix[2] == b'\'' {
                 let len = scan_triple_string(after_prefix, b'\'');
@@ -1594,6 +2523,15 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
 
         // Double-quoted name
         b'"' => {
+            if syntax.standard_strings {
+                let len = scan_standard_string(after_prefix);
+                return Some(LexResult {
+                    action: &A_QUOTED_NAME,
+                    match_len: prefix_len + len,
+                    prefix,
+                    token_text: &after_prefix_str[..len],
+                });
+            }
             if after_prefix.len() >= 3 && after_prefix[1] == b'"' && after_prefix[2] == b'"' {
                 let len = scan_triple_string(after_prefix, b'"');
                 Some(LexResult {
@@ -1604,8 +2542,13 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                 })
             } else {
                 let len = scan_string(after_prefix);
+                let action = if syntax.double_quoted_strings {
+                    &A_NAME
+                } else {
+                    &A_QUOTED_NAME
+                };
                 Some(LexResult {
-                    action: &A_QUOTED_NAME,
+                    action,
                     match_len: prefix_len + len,
                     prefix,
                     token_text: &after_prefix_str[..len],
@@ -1635,6 +2578,16 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                     token_text: &after_prefix_str[..ds_len],
                 });
             }
+            if syntax.widget_references && after_prefix.get(1) == Some(&b'{') {
+                if let Some(close) = memchr(b'}', after_prefix) {
+                    return Some(LexResult {
+                        action: &A_NAME,
+                        match_len: prefix_len + close + 1,
+                        prefix,
+                        token_text: &after_prefix_str[..close + 1],
+                    });
+                }
+            }
             // $identifier or $N
             let word_len = scan_word(&after_prefix[1..]);
             if word_len > 0 {
@@ -1766,6 +2719,15 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
             token_text: "(",
         }),
 
+        b'[' if syntax.bracket_identifiers => {
+            let len = scan_bracket_identifier(after_prefix);
+            Some(LexResult {
+                action: &A_QUOTED_NAME,
+                match_len: prefix_len + len,
+                prefix,
+                token_text: &after_prefix_str[..len],
+            })
+        }
         b'[' => Some(LexResult {
             action: &A_BRACKET_OPEN,
             match_len: prefix_len + 1,
@@ -1901,6 +2863,30 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
         }
 
         b'@' => {
+            if syntax.stage_references {
+                // Kept as written: stage paths are case-sensitive
+                let stage_len = scan_stage_reference(after_prefix);
+                if stage_len > 0 {
+                    return Some(LexResult {
+                        action: &A_QUOTED_NAME,
+                        match_len: prefix_len + stage_len,
+                        prefix,
+                        token_text: &after_prefix_str[..stage_len],
+                    });
+                }
+            }
+            if syntax.system_variables && after_prefix.starts_with(b"@@") {
+                let word_len = scan_word(&after_prefix[2..]);
+                if word_len > 0 {
+                    let total = 2 + word_len;
+                    return Some(LexResult {
+                        action: &A_NAME,
+                        match_len: prefix_len + total,
+                        prefix,
+                        token_text: &after_prefix_str[..total],
+                    });
+                }
+            }
             // Check for @variable first
             if after_prefix.len() >= 2
                 && (after_prefix[1].is_ascii_alphanumeric() || after_prefix[1] == b'_')
@@ -2026,23 +3012,53 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
             })
         }
 
-        // r/R prefix for raw strings (r"""...""", r'''...''')
-        b'r' | b'R' => {
-            if after_prefix.len() >= 4 {
-                if after_prefix[1] == b'"' && after_prefix[2] == b'"' && after_prefix[3] == b'"' {
-                    let len = 1 + scan_triple_string(&after_prefix[1..], b'"');
-                    return Some(LexResult {
-                        action: &A_NAME,
-                        match_len: prefix_len + len,
-                        prefix,
-                        token_text: &after_prefix_str[..len],
-                    });
-                }
-                if after_prefix[1] == b'\'' && after_prefix[2] == b'\'' && after_prefix[3] == b'\''
-                {
-                    let len = 1 + scan_triple_string(&after_prefix[1..], b'\'');
-                    return Some(LexResult {
-                        action: &A_NAME,
+        // E'...' escape strings keep backslash escapes under standard strings
+        b'e' | b'E' if syntax.standard_strings && after_prefix.get(1) == Some(&b'\'') => {
+            let len = 1 + scan_string(&after_prefix[1..]);
+            Some(LexResult {
+                action: &A_NAME,
+                match_len: prefix_len + len,
+                prefix,
+                token_text: &after_prefix_str[..len],
+            })
+        }
+
+        // U&'...' Unicode strings and U&"..." Unicode identifiers
+        b'u' | b'U'
+            if syntax.standard_strings
+                && matches!(after_prefix.get(1..3), Some(b"&'") | Some(b"&\"")) =>
+        {
+            let len = 2 + scan_standard_string(&after_prefix[2..]);
+            let action = if after_prefix[2] == b'"' {
+                &A_QUOTED_NAME
+            } else {
+                &A_NAME
+            };
+            Some(LexResult {
+                action,
+                match_len: prefix_len + len,
+                prefix,
+                token_text: &after_prefix_str[..len],
+            })
+        }
+
+        // r/R prefix for raw strings (r"""...""", r'''...''')
+        b'r' | b'R' => {
+            if after_prefix.len() >= 4 {
+                if after_prefix[1] == b'"' && after_prefix[2] == b'"' && after_prefix[3] == b'"' {
+                    let len = 1 + scan_triple_string(&after_prefix[1..], b'"');
+                    return Some(LexResult {
+                        action: &A_NAME,
+                        match_len: prefix_len + len,
+                        prefix,
+                        token_text: &after_prefix_str[..len],
+                    });
+                }
+                if after_prefix[1] == b'\'' && after_prefix[2] == b'\'' && after_prefix[3] == b'\''
+                {
+                    let len = 1 + scan_triple_string(&after_prefix[1..], b'\'');
+                    return Some(LexResult {
+                        action: &A_NAME,
                         match_len: prefix_len + len,
                         prefix,
                         token_text: &after_prefix_str[..len],
@@ -2057,6 +3073,7 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
                 after_prefix,
                 after_prefix_str,
                 state,
+                syntax,
             )
         }
 
@@ -2068,6 +3085,7 @@ pub fn lex_one<'a>(remaining: &'a str, state: LexState) -> Option<LexResult<'a>>
             after_prefix,
             after_prefix_str,
             state,
+            syntax,
         ),
 
         // Non-ASCII (unicode identifiers)
@@ -2109,8 +3127,14 @@ fn lex_identifier<'a>(
     after_prefix: &[u8],
     after_prefix_str: &'a str,
     state: LexState,
+    syntax: &Syntax,
 ) -> Option<LexResult<'a>> {
-    let word_len = scan_word(after_prefix);
+    let keywords = &syntax.keywords;
+    let word_len = if syntax.dollar_names {
+        scan_dollar_word(after_prefix)
+    } else {
+        scan_word(after_prefix)
+    };
     if word_len == 0 {
         return None;
     }
@@ -2135,8 +3159,22 @@ fn lex_identifier<'a>(
         });
     };
 
-    // Try multi-word extension
-    let (total_word_len, full_text) = if let Some(extra) = try_multi_word(lower, after_word, state)
+    // Try multi-word extension. T-SQL has no END IF / END WHILE, so an IF
+    // after a block's END starts the next statement.
+    let multi_word_state = if syntax.tsql_blocks && state == LexState::Block {
+        LexState::Main
+    } else {
+        state
+    };
+    let custom_match = syntax
+        .keywords
+        .custom
+        .as_ref()
+        .and_then(|custom| custom.try_phrase(lower, after_word));
+    let (total_word_len, full_text) = if let Some(extra) =
+        try_multi_word(lower, after_word, multi_word_state)
+            .max(try_multi_word_syntax(lower, after_word, syntax))
+            .max(custom_match)
     {
         (word_len + extra, &after_prefix_str[..word_len + extra])
     } else {
@@ -2174,6 +3212,26 @@ fn lex_identifier<'a>(
         unsafe { std::str::from_utf8_unchecked(&full_lower_buf[..out_len]) }
     };
 
+    // A phrase that only another dialect gives a meaning (e.g. ClickHouse
+    // `global join` in DuckDB, or Snowflake `one row per match`) is cut back
+    // to its longest prefix that is a keyword here, or to its first word.
+    let (total_word_len, full_text, classify_key) = if total_word_len != word_len
+        && !keywords.recognizes(classify_key, lower)
+        && (ALL_DIALECT_KEYWORDS.recognizes(classify_key, lower)
+            || SNOWFLAKE_UNTERM_KEYWORDS.contains(classify_key))
+    {
+        let key_len = classify_key
+            .rmatch_indices(' ')
+            .map(|(i, _)| i)
+            .find(|&i| is_keyword(&classify_key[..i], lower, keywords))
+            .unwrap_or(word_len);
+        let key = &classify_key[..key_len];
+        let text_len = phrase_len(full_text, key.split(' ').count());
+        (text_len, &full_text[..text_len], key)
+    } else {
+        (total_word_len, full_text, classify_key)
+    };
+
     // Check if followed by ( for keyword-before-paren handling
     let rest_after_keyword = &after_prefix[total_word_len..];
     let has_paren = has_trailing_paren(rest_after_keyword);
@@ -2183,10 +3241,10 @@ fn lex_identifier<'a>(
         classify_key,
         lower,
         full_text,
-        total_word_len,
         has_paren,
         state,
         after_prefix_str,
+        syntax,
     );
 
     Some(LexResult {
@@ -2197,6 +3255,31 @@ fn lex_identifier<'a>(
     })
 }
 
+/// Check if a (lowercased) phrase is a keyword, operator or reserved word in
+/// the shared tables or in `keywords`.
+fn is_keyword(kw: &str, first_word: &str, keywords: &Keywords) -> bool {
+    UNTERM_KEYWORDS.contains(kw)
+        || WORD_OPERATORS.contains(kw)
+        || SET_OPERATORS.contains(kw)
+        || UNSUPPORTED_DDL.contains(first_word)
+        || keywords.recognizes(kw, first_word)
+}
+
+/// Byte length of the first `words` whitespace-separated words of `text`.
+fn phrase_len(text: &str, words: usize) -> usize {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    for _ in 0..words {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+    }
+    i
+}
+
 /// Check if there's a `(` after optional whitespace.
 #[inline]
 fn has_trailing_paren(bytes: &[u8]) -> bool {
@@ -2212,11 +3295,12 @@ fn classify_keyword<'a>(
     full_lower: &str,
     first_word_lower: &str,
     full_text: &'a str,
-    _total_len: usize,
     has_paren: bool,
     state: LexState,
     after_prefix_str: &'a str,
+    syntax: &Syntax,
 ) -> (&'static Action, &'a str) {
+    let keywords = &syntax.keywords;
     // Angle bracket types: array<, struct<, map<, table<
     // Check if identifier is followed by < (possibly with whitespace)
     // Must be before state-specific dispatch so it works in all states (e.g., Function)
@@ -2236,12 +3320,59 @@ fn classify_keyword<'a>(
         }
     }
 
+    // T-SQL control flow replaces the procedural block keywords, and since
+    // statements need not end with a semicolon, it may follow any statement
+    if syntax.tsql_blocks && state != LexState::Unsupported {
+        if let Some(result) = classify_tsql_keyword(full_lower, full_text, state) {
+            return result;
+        }
+    }
+
     // State-specific keyword handling
     match state {
         LexState::Grant => return classify_grant_keyword(full_lower, full_text, has_paren),
         LexState::Function => return classify_function_keyword(full_lower, full_text, has_paren),
         LexState::Warehouse => return classify_warehouse_keyword(full_lower, full_text, has_paren),
         LexState::Clone => return classify_clone_keyword(full_lower, full_text, has_paren),
+        LexState::Insert => {
+            // The SELECT / VALUES body of an INSERT uses the main rules
+            if let Some(result) = classify_insert_keyword(full_lower, full_text) {
+                return result;
+            }
+        }
+        LexState::Update => {
+            // Assignments and FROM / WHERE clauses use the main rules
+            if let Some(result) = classify_update_keyword(full_lower, full_text) {
+                return result;
+            }
+        }
+        LexState::CreateTable => {
+            // Anything not specific to column definitions uses the main rules
+            if let Some(result) =
+                classify_create_table_keyword(full_lower, full_text, after_prefix_str)
+            {
+                return result;
+            }
+        }
+        LexState::Merge => {
+            // USING and the nested SET / VALUES lists use the main rules
+            if let Some(result) = classify_merge_keyword(full_lower, full_text) {
+                return result;
+            }
+        }
+        LexState::Block if syntax.scripting_blocks => {
+            // The statements inside a block use the main rules
+            let rest = &after_prefix_str.as_bytes()[full_text.len()..];
+            if let Some(result) = classify_block_keyword(full_lower, full_text, rest, syntax) {
+                return result;
+            }
+        }
+        LexState::Stage => {
+            // FROM and the nested queries use the main rules
+            if let Some(result) = classify_stage_keyword(full_lower, full_text) {
+                return result;
+            }
+        }
         _ => {} // Main state — fall through
     }
 
@@ -2315,13 +3446,42 @@ fn classify_keyword<'a>(
         return (&A_RESERVED_UNTERM, full_text);
     }
 
+    // LIMIT offset, count — likewise dynamic
+    if full_lower.starts_with("limit ") {
+        return (&A_RESERVED_UNTERM, full_text);
+    }
+
+    // MATCH_RECOGNIZE PATTERN is only a clause when its regex follows
+    if full_lower == "pattern" && !has_paren {
+        return (&A_NAME, full_text);
+    }
+
+    // Time zone qualifiers of a type (`timestamp with time zone`) and
+    // `unnest(...) with ordinality` do not start a CTE
+    if matches!(
+        full_lower,
+        "with time zone" | "without time zone" | "with ordinality"
+    ) {
+        return (&A_NAME, full_text);
+    }
+
+    // Databricks OPTIMIZE is a statement, and a name anywhere else
+    if full_lower == "optimize" && keywords.is_unterm_keyword(full_lower) {
+        return (&A_RESERVED_STATEMENT_UNTERM, full_text);
+    }
+
+    // MATCH_RECOGNIZE clauses are only keywords inside its brackets
+    if SNOWFLAKE_UNTERM_KEYWORDS.contains(full_lower) && keywords.is_unterm_keyword(full_lower) {
+        return (&A_RESERVED_MATCH_RECOGNIZE_UNTERM, full_text);
+    }
+
     // Unterminated keywords (SELECT, JOIN variants, WHERE, etc.)
-    if is_unterm_keyword(full_lower) {
+    if is_unterm_keyword(full_lower, keywords) {
         return (&A_RESERVED_UNTERM, full_text);
     }
 
     // Word operators
-    if is_word_operator(full_lower) {
+    if is_word_operator(full_lower, keywords) {
         return (&A_RESERVED_WORD_OP, full_text);
     }
 
@@ -2347,7 +3507,7 @@ fn classify_keyword<'a>(
     }
 
     // Set operators
-    if is_set_operator(full_lower) {
+    if is_set_operator(full_lower, keywords) {
         return (&A_RESERVED_SET_OP, full_text);
     }
 
@@ -2359,6 +3519,21 @@ fn classify_keyword<'a>(
         return (&A_RESERVED_NONRESERVED_UNTERM, full_text);
     }
 
+    // INSERT → INSERT ruleset
+    if first_word_lower == "insert" {
+        return (&A_RESERVED_NONRESERVED_INSERT, full_text);
+    }
+
+    // MERGE → MERGE ruleset
+    if first_word_lower == "merge" {
+        return (&A_RESERVED_NONRESERVED_MERGE, full_text);
+    }
+
+    // UPDATE → UPDATE ruleset
+    if first_word_lower == "update" {
+        return (&A_RESERVED_NONRESERVED_UPDATE, full_text);
+    }
+
     // GRANT/REVOKE → GRANT ruleset
     if matches!(first_word_lower, "grant" | "revoke") {
         return (&A_RESERVED_NONRESERVED_GRANT, full_text);
@@ -2378,6 +3553,11 @@ fn classify_keyword<'a>(
             return (&A_RESERVED_NONRESERVED_FUNCTION, full_text);
         }
 
+        // CREATE TABLE with a column definition list
+        if first_word_lower == "create" && scan_rest_for_create_table(rest_bytes) {
+            return (&A_RESERVED_NONRESERVED_CREATE_TABLE, full_text);
+        }
+
         // Warehouse DDL (multi-word matching already extends to include "warehouse")
         if is_warehouse_ddl(full_lower) {
             return (&A_RESERVED_NONRESERVED_WAREHOUSE, full_text);
@@ -2391,12 +3571,43 @@ fn classify_keyword<'a>(
             return (&A_RESERVED_NONRESERVED_WAREHOUSE, full_text);
         }
 
+        // Snowflake CREATE STAGE / PIPE / STREAM / TASK
+        if syntax.stage_statements
+            && first_word_lower == "create"
+            && scan_rest_for_stage_object(rest_bytes)
+        {
+            return (&A_RESERVED_NONRESERVED_STAGE, full_text);
+        }
+
         // Generic unsupported DDL
         return (&A_RESERVED_NONRESERVED_UNSUPPORTED, full_text);
     }
 
+    // Procedural blocks → BLOCK ruleset
+    if syntax.scripting_blocks {
+        if matches!(first_word_lower, "if" | "while" | "for" | "loop" | "repeat") {
+            return (&A_RESERVED_BLOCK_STATEMENT, full_text);
+        }
+        let rest_bytes = &after_prefix_str.as_bytes()[full_text.len()..];
+        if (full_lower == "begin" && !is_transaction_begin(rest_bytes))
+            || (full_lower == "declare" && syntax.declare_sections)
+        {
+            return (&A_RESERVED_BLOCK_STATEMENT, full_text);
+        }
+    }
+
+    // DECLARE <variable> and EXECUTE IMMEDIATE are single statements
+    if matches!(full_lower, "declare" | "execute immediate") {
+        return (&A_RESERVED_NONRESERVED_UNTERM, full_text);
+    }
+
+    // Snowflake COPY INTO → STAGE ruleset
+    if syntax.stage_statements && full_lower == "copy into" {
+        return (&A_RESERVED_NONRESERVED_STAGE, full_text);
+    }
+
     // Other unsupported DDL keywords
-    if is_unsupported_ddl(first_word_lower) {
+    if is_unsupported_ddl(first_word_lower, keywords) {
         return (&A_RESERVED_NONRESERVED_UNSUPPORTED, full_text);
     }
 
@@ -2409,12 +3620,6 @@ static UNTERM_KEYWORDS: phf::Set<&'static str> = phf::phf_set! {
     "with recursive", "with",
     "select as struct", "select as value", "select all",
     "select distinct", "select",
-    "global inner join", "global left outer join", "global left join",
-    "global right outer join", "global right join", "global full outer join",
-    "global full join", "global any join", "global join",
-    "any left outer join", "any left join", "any right outer join", "any right join",
-    "any inner join", "any full outer join", "any full join",
-    "paste join",
     "natural full outer join", "natural full join", "natural left outer join",
     "natural left join", "natural right outer join", "natural right join",
     "natural inner join", "natural join",
@@ -2423,10 +3628,8 @@ static UNTERM_KEYWORDS: phf::Set<&'static str> = phf::phf_set! {
     "right outer join", "right semi join", "right anti join", "right join",
     "full outer join", "full join",
     "inner join", "semi join", "anti join", "asof left join", "asof join",
-    "positional join", "any join", "lateral join", "join",
-    "lateral view outer", "lateral view", "lateral",
-    "prewhere", "where",
-    "group by", "cluster by", "distribute by", "sort by",
+    "lateral join", "join", "lateral",
+    "where", "group by",
     "having", "qualify", "window",
     "order by", "limit",
     "fetch first", "fetch next",
@@ -2438,8 +3641,8 @@ static UNTERM_KEYWORDS: phf::Set<&'static str> = phf::phf_set! {
 };
 
 /// Check if a (lowercased) keyword is an unterminated keyword.
-fn is_unterm_keyword(kw: &str) -> bool {
-    UNTERM_KEYWORDS.contains(kw)
+fn is_unterm_keyword(kw: &str, keywords: &Keywords) -> bool {
+    UNTERM_KEYWORDS.contains(kw) || keywords.is_unterm_keyword(kw)
 }
 
 /// Perfect-hash set of word operators (lowercased).
@@ -2462,8 +3665,6 @@ static WORD_OPERATORS: phf::Set<&'static str> = phf::phf_set! {
     "not rlike",
     "not regexp",
     "not exists",
-    "global not in",
-    "global in",
     "not in",
     "is not",
     "grouping sets",
@@ -2497,14 +3698,12 @@ static WORD_OPERATORS: phf::Set<&'static str> = phf::phf_set! {
 };
 
 /// Check if it's a word operator.
-fn is_word_operator(kw: &str) -> bool {
-    WORD_OPERATORS.contains(kw)
+fn is_word_operator(kw: &str, keywords: &Keywords) -> bool {
+    WORD_OPERATORS.contains(kw) || keywords.is_word_operator(kw)
 }
 
 /// Perfect-hash set of set operators (lowercased).
 static SET_OPERATORS: phf::Set<&'static str> = phf::phf_set! {
-    "union all by name",
-    "union by name",
     "union all",
     "union distinct",
     "intersect all",
@@ -2526,15 +3725,14 @@ static SET_OPERATORS: phf::Set<&'static str> = phf::phf_set! {
 };
 
 /// Check if it's a set operator.
-fn is_set_operator(kw: &str) -> bool {
-    SET_OPERATORS.contains(kw)
+fn is_set_operator(kw: &str, keywords: &Keywords) -> bool {
+    SET_OPERATORS.contains(kw) || keywords.is_set_operator(kw)
 }
 
-/// Check if it's a CREATE/ALTER FUNCTION DDL keyword.
+/// Check if it's a CREATE/ALTER/DROP FUNCTION or PROCEDURE DDL keyword.
 fn is_function_ddl(kw: &str) -> bool {
-    kw.starts_with("create") && kw.contains("function")
-        || kw.starts_with("alter") && kw.contains("function")
-        || kw.starts_with("drop") && kw.contains("function")
+    matches!(kw.split(' ').next(), Some("create" | "alter" | "drop"))
+        && (kw.contains("function") || kw.contains("procedure"))
 }
 
 /// Check if it's a CREATE/ALTER WAREHOUSE DDL keyword.
@@ -2545,21 +3743,96 @@ fn is_warehouse_ddl(kw: &str) -> bool {
 
 /// Perfect-hash set of unsupported DDL first words (lowercased).
 static UNSUPPORTED_DDL: phf::Set<&'static str> = phf::phf_set! {
-    "delete", "insert", "update", "merge", "truncate", "rename", "unset",
+    "delete", "truncate", "rename", "unset",
     "use", "execute", "begin", "commit", "rollback", "copy", "clone",
-    "cluster", "deallocate", "declare", "discard", "do", "export",
+    "cluster", "deallocate", "discard", "do", "export",
     "handler", "import", "lock", "move", "prepare", "reassign", "repair",
     "security", "unload", "validate", "vacuum", "analyze", "refresh",
-    "list", "remove", "get", "put", "describe", "show", "comment",
-    "add", "undrop", "cache", "clear",
+    "describe", "show", "comment", "add",
 };
 
 /// Check if first word starts an unsupported DDL.
 /// Note: create/alter/drop and grant/revoke are handled separately above.
-fn is_unsupported_ddl(first_word: &str) -> bool {
-    UNSUPPORTED_DDL.contains(first_word)
+fn is_unsupported_ddl(first_word: &str, keywords: &Keywords) -> bool {
+    UNSUPPORTED_DDL.contains(first_word) || keywords.is_reserved_word(first_word)
 }
 
+// ---- Dialect-specific keyword tables ----
+
+/// ClickHouse join modifiers and clauses.
+pub static CLICKHOUSE_UNTERM_KEYWORDS: KeywordSet = phf::phf_set! {
+    "global inner join", "global left outer join", "global left join",
+    "global right outer join", "global right join", "global full outer join",
+    "global full join", "global any join", "global join",
+    "any left outer join", "any left join", "any right outer join", "any right join",
+    "any inner join", "any full outer join", "any full join", "any join",
+    "paste join",
+    "prewhere",
+};
+
+/// ClickHouse distributed subquery operators.
+pub static CLICKHOUSE_WORD_OPERATORS: KeywordSet = phf::phf_set! {
+    "global not in", "global in",
+};
+
+/// DuckDB joins.
+pub static DUCKDB_UNTERM_KEYWORDS: KeywordSet = phf::phf_set! {
+    "positional join",
+};
+
+/// DuckDB set operators that match columns by name.
+pub static DUCKDB_SET_OPERATORS: KeywordSet = phf::phf_set! {
+    "union all by name", "union by name",
+};
+
+/// SparkSQL / Databricks clauses.
+pub static SPARK_UNTERM_KEYWORDS: KeywordSet = phf::phf_set! {
+    "lateral view outer", "lateral view",
+    "cluster by", "distribute by", "sort by",
+    "optimize", "zorder by",
+};
+
+/// SparkSQL / Databricks statements sqlfmt does not format.
+pub static SPARK_RESERVED_WORDS: KeywordSet = phf::phf_set! {
+    "cache", "clear",
+};
+
+/// MySQL join hint, as a join and as a SELECT modifier.
+pub static MYSQL_UNTERM_KEYWORDS: KeywordSet = phf::phf_set! {
+    "straight_join", "select straight_join",
+};
+
+/// T-SQL OUTPUT clauses of INSERT, UPDATE, DELETE and MERGE.
+pub static TSQL_UNTERM_KEYWORDS: KeywordSet = phf::phf_set! {
+    "output",
+};
+
+/// Snowflake MATCH_RECOGNIZE clauses.
+pub static SNOWFLAKE_UNTERM_KEYWORDS: KeywordSet = phf::phf_set! {
+    "measures", "one row per match", "all rows per match", "after match skip", "pattern",
+    "define",
+};
+
+/// Snowflake statements sqlfmt does not format.
+pub static SNOWFLAKE_RESERVED_WORDS: KeywordSet = phf::phf_set! {
+    "list", "remove", "get", "put", "undrop",
+};
+
+/// The keywords of every dialect, except the MATCH_RECOGNIZE clauses that
+/// are only keywords inside its brackets. Used by the Polyglot dialect.
+pub static ALL_DIALECT_KEYWORDS: Keywords = Keywords {
+    unterm_keywords: &[
+        &CLICKHOUSE_UNTERM_KEYWORDS,
+        &DUCKDB_UNTERM_KEYWORDS,
+        &SPARK_UNTERM_KEYWORDS,
+        &MYSQL_UNTERM_KEYWORDS,
+    ],
+    word_operators: &[&CLICKHOUSE_WORD_OPERATORS],
+    set_operators: &[&DUCKDB_SET_OPERATORS],
+    reserved_words: &[&SNOWFLAKE_RESERVED_WORDS, &SPARK_RESERVED_WORDS],
+    custom: None,
+};
+
 /// Try to scan a frame clause: "ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW" etc.
 fn try_scan_frame_clause(text: &str) -> Option<usize> {
     let bytes = text.as_bytes();
@@ -2693,6 +3966,7 @@ fn lex_fmt_off<'a>(
     prefix_len: usize,
     after_prefix: &[u8],
     after_prefix_str: &'a str,
+    syntax: &Syntax,
 ) -> Option<LexResult<'a>> {
     if after_prefix[0] == b'\n' {
         return Some(LexResult {
@@ -2705,7 +3979,7 @@ fn lex_fmt_off<'a>(
 
     // Check for fmt:on (-- fmt: on or # fmt: on)
     if (after_prefix.len() >= 2 && after_prefix[0] == b'-' && after_prefix[1] == b'-')
-        || after_prefix[0] == b'#'
+        || (syntax.hash_comments && after_prefix[0] == b'#')
     {
         let comment_len = scan_line_comment(after_prefix);
         let comment_text = &after_prefix[..comment_len];
@@ -2861,6 +4135,7 @@ fn lex_unsupported<'a>(
     prefix_len: usize,
     after_prefix: &[u8],
     after_prefix_str: &'a str,
+    syntax: &Syntax,
 ) -> Option<LexResult<'a>> {
     let b0 = after_prefix[0];
 
@@ -2886,7 +4161,7 @@ fn lex_unsupported<'a>(
 
     // fmt:off/on comments
     if (b0 == b'-' && after_prefix.len() >= 2 && after_prefix[1] == b'-')
-        || b0 == b'#'
+        || (syntax.hash_comments && b0 == b'#')
         || (b0 == b'/' && after_prefix.len() >= 2 && after_prefix[1] == b'/')
     {
         let comment_len = scan_line_comment(after_prefix);
@@ -2961,11 +4236,26 @@ fn lex_unsupported<'a>(
         });
     }
 
-    // Data: everything up to ; or newline
-    let data_len = after_prefix
+    // Flow operator: pipes the unsupported statement into the next one
+    if syntax.flow_operator && after_prefix.starts_with(b"->>") {
+        return Some(LexResult {
+            action: &A_FLOW_OP,
+            match_len: prefix_len + 3,
+            prefix,
+            token_text: &after_prefix_str[..3],
+        });
+    }
+
+    // Data: everything up to ; or newline (or ->> where it is an operator)
+    let mut data_len = after_prefix
         .iter()
         .position(|&b| b == b';' || b == b'\n')
         .unwrap_or(after_prefix.len());
+    if syntax.flow_operator {
+        if let Some(flow) = memchr::memmem::find(&after_prefix[..data_len], b"->>") {
+            data_len = after_prefix[..flow].trim_ascii_end().len();
+        }
+    }
     if data_len > 0 {
         Some(LexResult {
             action: &A_RESERVED_DATA,
@@ -3035,9 +4325,8 @@ fn is_function_unterm_keyword(kw: &str) -> bool {
             | "options"
             | "cascade"
             | "restrict"
-    ) || kw.starts_with("create") && kw.contains("function")
-        || kw.starts_with("alter") && kw.contains("function")
-        || kw.starts_with("drop") && kw.contains("function")
+    ) || is_function_ddl(kw)
+        || kw.starts_with("execute as")
         || kw.starts_with("return")
         || kw.starts_with("leakproof")
         || kw.starts_with("not leakproof")
@@ -3114,10 +4403,350 @@ fn classify_clone_keyword<'a>(
     (&A_NAME, text)
 }
 
+/// Keywords specific to INSERT statements. Returns None for anything else,
+/// so the column list and SELECT/VALUES body are lexed with the main rules.
+fn classify_insert_keyword<'a>(kw: &str, text: &'a str) -> Option<(&'static Action, &'a str)> {
+    if kw == "insert"
+        || kw.starts_with("insert ")
+        || matches!(
+            kw,
+            "on conflict"
+                | "do nothing"
+                | "do update set"
+                | "default values"
+                | "overriding system value"
+                | "overriding user value"
+        )
+    {
+        return Some((&A_RESERVED_UNTERM, text));
+    }
+    // MySQL ON DUPLICATE KEY UPDATE is lexed like an UPDATE's SET list
+    if kw == "on duplicate key update" {
+        return Some((&A_RESERVED_LEX_UPDATE, text));
+    }
+    None
+}
+
+/// Keywords specific to UPDATE statements. Returns None for anything else.
+fn classify_update_keyword<'a>(kw: &str, text: &'a str) -> Option<(&'static Action, &'a str)> {
+    if matches!(kw, "update" | "set" | "on duplicate key update") {
+        return Some((&A_RESERVED_UNTERM, text));
+    }
+    // MySQL `values(col)` refers to the row an INSERT would have added
+    if kw == "values" {
+        return Some((&A_RESERVED_UPDATE_VALUES, text));
+    }
+    None
+}
+
+/// Keywords specific to CREATE TABLE statements. Constraints and other
+/// column-definition keywords become DdlKeywords so they are lowercased
+/// without splitting the definition across lines. Column types become
+/// DataTypes that take in their precision and scale, so `decimal(12, 2)` is
+/// never split; column names stay Names.
+fn classify_create_table_keyword<'a>(
+    kw: &str,
+    text: &'a str,
+    after_prefix_str: &'a str,
+) -> Option<(&'static Action, &'a str)> {
+    if kw.starts_with("create ") {
+        return Some((&A_RESERVED_UNTERM, text));
+    }
+    // Table options following the column list
+    if matches!(
+        kw,
+        "engine"
+            | "inherits"
+            | "tablespace"
+            | "using"
+            | "partitioned by"
+            | "clustered by"
+            | "cluster by"
+            | "location"
+            | "options"
+            | "tblproperties"
+    ) {
+        return Some((&A_RESERVED_NONRESERVED_UNTERM, text));
+    }
+    if kw == "comment" {
+        return Some((&A_RESERVED_CREATE_TABLE_COMMENT, text));
+    }
+    // Interval types (`interval day to second`) are not the INTERVAL operator
+    if kw == "interval" {
+        return Some((&A_NAME, text));
+    }
+    if matches!(
+        kw,
+        "constraint"
+            | "not null"
+            | "null"
+            | "default"
+            | "primary key"
+            | "foreign key"
+            | "references"
+            | "unique"
+            | "check"
+            | "collate"
+            | "identity"
+            | "autoincrement"
+            | "auto_increment"
+            | "generated always as identity"
+            | "generated by default as identity"
+            | "generated always as"
+            | "stored"
+            | "on delete"
+            | "on update"
+            | "cascade"
+            | "restrict"
+            | "set null"
+            | "set default"
+            | "no action"
+            | "deferrable"
+            | "not deferrable"
+            | "initially deferred"
+            | "initially immediate"
+            | "with time zone"
+            | "without time zone"
+            | "on commit preserve rows"
+            | "on commit delete rows"
+            | "on commit drop"
+    ) {
+        return Some((&A_RESERVED_DDL_KEYWORD, text));
+    }
+    if !kw.contains(' ') {
+        let rest = &after_prefix_str.as_bytes()[text.len()..];
+        if let Some(len) = type_params_len(rest) {
+            return Some((&A_RESERVED_DATA_TYPE, &after_prefix_str[..text.len() + len]));
+        }
+        if DATA_TYPES.contains(kw) && ends_column_type(rest) {
+            return Some((&A_RESERVED_DATA_TYPE, text));
+        }
+    }
+    None
+}
+
+/// Built-in column types, recognized without parameters when they end the
+/// column type (a column may share a type's name, as in `date date`).
+static DATA_TYPES: phf::Set<&'static str> = phf::phf_set! {
+    "bigint", "bigserial", "binary", "bit", "blob", "bool", "boolean", "bytea",
+    "bytes", "char", "character", "date", "datetime", "decimal", "double",
+    "float", "float4", "float8", "float32", "float64", "int", "int2", "int4",
+    "int8", "int16", "int32", "int64", "integer", "json", "jsonb", "nchar",
+    "number", "numeric", "nvarchar", "real", "serial", "smallint", "string",
+    "text", "time", "timestamp", "timestamptz", "timestamp_ltz", "timestamp_ntz",
+    "timestamp_tz", "tinyint", "uint8", "uint16", "uint32", "uint64", "uuid",
+    "varbinary", "varchar", "varchar2", "variant",
+};
+
+/// Length of the precision and scale after a column type, e.g. ` (12, 2)`
+/// in `decimal (12, 2)`: numbers or `max`, each optionally followed by
+/// `byte` or `char`. None if `rest` does not start with such a list.
+fn type_params_len(rest: &[u8]) -> Option<usize> {
+    let open = rest
+        .iter()
+        .position(|&b| !matches!(b, b' ' | b'\t'))
+        .filter(|&i| rest[i] == b'(')?;
+    let close = open + rest[open..].iter().position(|&b| b == b')')?;
+    let params = std::str::from_utf8(&rest[open + 1..close]).ok()?;
+    let is_param = |param: &str| {
+        let mut words = param.split_whitespace();
+        words
+            .next()
+            .is_some_and(|w| w.bytes().all(|b| b.is_ascii_digit()) || w.eq_ignore_ascii_case("max"))
+            && words
+                .next()
+                .is_none_or(|w| w.eq_ignore_ascii_case("byte") || w.eq_ignore_ascii_case("char"))
+            && words.next().is_none()
+    };
+    params.split(',').all(is_param).then_some(close + 1)
+}
+
+/// True if a column type ends at `rest`: at a comma, a closing bracket, an
+/// array suffix or a column constraint, rather than being a column name
+/// followed by its type.
+fn ends_column_type(rest: &[u8]) -> bool {
+    let start = rest
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(rest.len());
+    let rest = &rest[start..];
+    if matches!(rest.first(), None | Some(b',' | b')' | b'[' | b';')) {
+        return true;
+    }
+    let word_len = rest
+        .iter()
+        .position(|b| !b.is_ascii_alphanumeric() && *b != b'_')
+        .unwrap_or(rest.len());
+    let word = std::str::from_utf8(&rest[..word_len])
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    matches!(
+        word.as_str(),
+        "not"
+            | "null"
+            | "default"
+            | "primary"
+            | "references"
+            | "unique"
+            | "check"
+            | "collate"
+            | "constraint"
+            | "generated"
+            | "identity"
+            | "autoincrement"
+            | "auto_increment"
+            | "comment"
+            | "with"
+            | "without"
+    )
+}
+
+/// Keywords of procedural blocks (Snowflake Scripting, BigQuery scripting).
+/// Returns None for anything else, including the statements inside a block.
+fn classify_block_keyword<'a>(
+    kw: &str,
+    text: &'a str,
+    rest: &[u8],
+    syntax: &Syntax,
+) -> Option<(&'static Action, &'a str)> {
+    let action = match kw {
+        "if" | "while" | "for" | "repeat" => &A_RESERVED_STATEMENT_BLOCK_START,
+        "begin" if !is_transaction_begin(rest) => &A_RESERVED_STATEMENT_BLOCK_START,
+        "declare" if syntax.declare_sections => &A_RESERVED_STATEMENT_BLOCK_START,
+        "declare" | "until" | "raise using message" => &A_RESERVED_UNTERM,
+        "loop" => &A_RESERVED_BLOCK_LOOP,
+        "then" | "else" | "when" => &A_RESERVED_BLOCK_OR_CASE_KEYWORD,
+        "do" | "elseif" | "elsif" | "exception" => &A_RESERVED_TOP_LEVEL_BLOCK_KEYWORD,
+        "end" => &A_RESERVED_BLOCK_OR_CASE_END,
+        "end if" | "end loop" | "end while" | "end for" | "end repeat" => &A_RESERVED_BLOCK_END,
+        _ => return None,
+    };
+    Some((action, text))
+}
+
+/// T-SQL control flow keywords. Returns None for anything else.
+fn classify_tsql_keyword<'a>(
+    kw: &str,
+    text: &'a str,
+    state: LexState,
+) -> Option<(&'static Action, &'a str)> {
+    let action = match kw {
+        // The condition is a clause; the statement or block follows it
+        "if" | "while" | "else" => &A_RESERVED_UNTERM,
+        "begin" | "begin tran" | "begin transaction" if state == LexState::Block => {
+            &A_RESERVED_BLOCK_START
+        }
+        "begin" | "begin tran" | "begin transaction" => &A_RESERVED_LEX_BLOCK,
+        "end" => &A_RESERVED_BLOCK_OR_CASE_END,
+        "commit"
+        | "commit tran"
+        | "commit transaction"
+        | "rollback"
+        | "rollback tran"
+        | "rollback transaction"
+            if state != LexState::Main =>
+        {
+            &A_RESERVED_BLOCK_END
+        }
+        _ => return None,
+    };
+    Some((action, text))
+}
+
+/// True if the rest of a BEGIN statement makes it a transaction
+/// (`begin;`, `begin transaction`, `begin work`, ...) rather than a block.
+fn is_transaction_begin(rest: &[u8]) -> bool {
+    let start = rest
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(rest.len());
+    let rest = &rest[start..];
+    if rest.is_empty() || rest[0] == b';' {
+        return true;
+    }
+    let word_len = scan_word(rest);
+    [
+        "transaction",
+        "work",
+        "tran",
+        "isolation",
+        "deferred",
+        "immediate",
+        "exclusive",
+    ]
+    .iter()
+    .any(|kw| rest[..word_len].eq_ignore_ascii_case(kw.as_bytes()))
+}
+
+/// Keywords specific to MERGE statements. Each `when [not] matched` branch
+/// and its `then <action>` start a new clause. Returns None for anything else.
+fn classify_merge_keyword<'a>(kw: &str, text: &'a str) -> Option<(&'static Action, &'a str)> {
+    if kw == "on" {
+        return Some((&A_RESERVED_MERGE_ON, text));
+    }
+    if matches!(
+        kw,
+        "merge" | "merge into" | "merge with schema evolution into"
+    ) || kw.starts_with("when matched")
+        || kw.starts_with("when not matched")
+        || matches!(
+            kw,
+            "then update set"
+                | "then insert by name"
+                | "then insert"
+                | "then delete"
+                | "then do nothing"
+        )
+    {
+        return Some((&A_RESERVED_UNTERM, text));
+    }
+    None
+}
+
+/// Classify keywords in Snowflake COPY INTO and CREATE STAGE, PIPE, STREAM
+/// and TASK statements: each option starts a line, and the AS of a pipe or
+/// task hands its body back to the main rules.
+fn classify_stage_keyword<'a>(kw: &str, text: &'a str) -> Option<(&'static Action, &'a str)> {
+    if kw == "copy into" || kw.starts_with("create") {
+        return Some((&A_RESERVED_UNTERM, text));
+    }
+    if kw == "as" {
+        return Some((&A_RESERVED_STAGE_AS, text));
+    }
+    if STAGE_OPTIONS.contains(kw) {
+        return Some((&A_RESERVED_STAGE_OPTION, text));
+    }
+    None
+}
+
+/// Perfect-hash set of COPY INTO, stage, pipe, stream and task options.
+static STAGE_OPTIONS: phf::Set<&'static str> = phf::phf_set! {
+    // COPY INTO
+    "file_format", "files", "pattern", "validation_mode", "on_error",
+    "size_limit", "purge", "return_failed_only", "match_by_column_name",
+    "include_metadata", "enforce_length", "truncatecolumns", "force",
+    "load_uncertain_files", "load_mode", "overwrite", "single", "max_file_size",
+    "include_query_id", "detailed_output", "header",
+    // Stages
+    "url", "storage_integration", "credentials", "encryption", "copy_options",
+    "directory", "comment", "tag",
+    // Pipes and streams
+    "auto_ingest", "error_integration", "aws_sns_topic", "integration",
+    "append_only", "insert_only", "show_initial_rows",
+    // Tasks
+    "warehouse", "user_task_managed_initial_warehouse_size", "schedule", "config",
+    "allow_overlapping_execution", "user_task_timeout_ms",
+    "suspend_task_after_num_failures", "task_auto_retry_attempts", "finalize", "after",
+};
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn polyglot() -> Syntax {
+        Syntax::new(ALL_DIALECT_KEYWORDS.clone())
+    }
+
     #[test]
     fn test_skip_prefix_whitespace() {
         assert_eq!(skip_prefix_whitespace(b"  hello"), 2);
@@ -3155,64 +4784,64 @@ mod tests {
 
     #[test]
     fn test_lex_simple_tokens() {
-        let r = lex_one("  SELECT", LexState::Main).unwrap();
+        let r = lex_one("  SELECT", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.prefix, "  ");
         assert_eq!(r.token_text.to_ascii_lowercase(), "select");
 
-        let r = lex_one(",", LexState::Main).unwrap();
+        let r = lex_one(",", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, ",");
 
-        let r = lex_one(";", LexState::Main).unwrap();
+        let r = lex_one(";", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, ";");
 
-        let r = lex_one("\n", LexState::Main).unwrap();
+        let r = lex_one("\n", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "\n");
     }
 
     #[test]
     fn test_lex_keyword_classification() {
-        let r = lex_one("select", LexState::Main).unwrap();
+        let r = lex_one("select", LexState::Main, &polyglot()).unwrap();
         // Should be classified as HandleReservedKeyword wrapping UntermKeyword
         assert!(matches!(r.action, Action::HandleReservedKeyword { .. }));
 
-        let r = lex_one("from", LexState::Main).unwrap();
+        let r = lex_one("from", LexState::Main, &polyglot()).unwrap();
         assert!(matches!(r.action, Action::HandleReservedKeyword { .. }));
     }
 
     #[test]
     fn test_lex_multi_word() {
-        let r = lex_one("left outer join", LexState::Main).unwrap();
+        let r = lex_one("left outer join", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text.to_ascii_lowercase(), "left outer join");
 
-        let r = lex_one("order by", LexState::Main).unwrap();
+        let r = lex_one("order by", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text.to_ascii_lowercase(), "order by");
 
-        let r = lex_one("union all", LexState::Main).unwrap();
+        let r = lex_one("union all", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text.to_ascii_lowercase(), "union all");
     }
 
     #[test]
     fn test_lex_string_literals() {
-        let r = lex_one("'hello'", LexState::Main).unwrap();
+        let r = lex_one("'hello'", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "'hello'");
 
-        let r = lex_one("\"my_table\"", LexState::Main).unwrap();
+        let r = lex_one("\"my_table\"", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "\"my_table\"");
     }
 
     #[test]
     fn test_lex_comments() {
-        let r = lex_one("-- comment\n", LexState::Main).unwrap();
+        let r = lex_one("-- comment\n", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "-- comment");
         assert!(matches!(r.action, Action::AddComment));
 
-        let r = lex_one("/* block */", LexState::Main).unwrap();
+        let r = lex_one("/* block */", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "/* block */");
     }
 
     #[test]
     fn test_lex_fmt_markers() {
-        let r = lex_one("-- fmt: off", LexState::Main).unwrap();
+        let r = lex_one("-- fmt: off", LexState::Main, &polyglot()).unwrap();
         assert!(matches!(
             r.action,
             Action::AddNode {
@@ -3220,7 +4849,7 @@ mod tests {
             }
         ));
 
-        let r = lex_one("-- fmt: on", LexState::Main).unwrap();
+        let r = lex_one("-- fmt: on", LexState::Main, &polyglot()).unwrap();
         assert!(matches!(
             r.action,
             Action::AddNode {
@@ -3231,26 +4860,26 @@ mod tests {
 
     #[test]
     fn test_lex_jinja() {
-        let r = lex_one("{{ x }}", LexState::Main).unwrap();
+        let r = lex_one("{{ x }}", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "{{ x }}");
 
-        let r = lex_one("{% if x %}", LexState::Main).unwrap();
+        let r = lex_one("{% if x %}", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "{% if x %}");
         assert!(matches!(r.action, Action::HandleJinjaBlockStart));
     }
 
     #[test]
     fn test_lex_operators() {
-        let r = lex_one(">=", LexState::Main).unwrap();
+        let r = lex_one(">=", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, ">=");
 
-        let r = lex_one("::text", LexState::Main).unwrap();
+        let r = lex_one("::text", LexState::Main, &polyglot()).unwrap();
         assert_eq!(r.token_text, "::");
     }
 
     #[test]
     fn test_lex_fmt_off_mode() {
-        let r = lex_one("anything here", LexState::FmtOff).unwrap();
+        let r = lex_one("anything here", LexState::FmtOff, &polyglot()).unwrap();
         assert_eq!(r.token_text, "anything here");
         assert!(matches!(
             r.action,
@@ -3259,7 +4888,7 @@ mod tests {
             }
         ));
 
-        let r = lex_one("-- fmt: on", LexState::FmtOff).unwrap();
+        let r = lex_one("-- fmt: on", LexState::FmtOff, &polyglot()).unwrap();
         assert!(matches!(
             r.action,
             Action::AddNode {
@@ -3298,6 +4927,7 @@ mod tests {
         let r = lex_one(
             "rows between unbounded preceding and current row",
             LexState::Main,
+            &polyglot(),
         );
         assert!(r.is_some());
         let r = r.unwrap();
@@ -3309,15 +4939,743 @@ mod tests {
 
     #[test]
     fn test_select_top_multiline() {
-        let r = lex_one("select\ntop\n25\n*\n", LexState::Main).unwrap();
+        let r = lex_one("select\ntop\n25\n*\n", LexState::Main, &polyglot()).unwrap();
         eprintln!("token_text: {:?}, match_len: {}", r.token_text, r.match_len);
         assert_eq!(r.token_text, "select\ntop\n25");
     }
 
     #[test]
     fn test_union_all_multiline() {
-        let r = lex_one("union\nall\n", LexState::Main).unwrap();
+        let r = lex_one("union\nall\n", LexState::Main, &polyglot()).unwrap();
         eprintln!("token_text: {:?}, match_len: {}", r.token_text, r.match_len);
         assert_eq!(r.token_text, "union\nall");
     }
+
+    #[test]
+    fn test_insert_ruleset() {
+        let r = lex_one("INSERT OVERWRITE INTO t", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(r.action, Action::HandleReservedKeyword { .. }));
+        assert_eq!(r.token_text, "INSERT OVERWRITE INTO");
+
+        let r = lex_one("insert into t", LexState::Insert, &polyglot()).unwrap();
+        assert!(matches!(r.action, Action::HandleReservedKeyword { .. }));
+        assert_eq!(r.token_text, "insert into");
+
+        let r = lex_one("on conflict (id)", LexState::Insert, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "on conflict");
+
+        // The SELECT body falls through to the main rules
+        let r = lex_one("select a", LexState::Insert, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "select");
+        let r = lex_one("on a.id = b.id", LexState::Insert, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "on");
+
+        // Names that merely start with "insert" are still names
+        let r = lex_one("inserted_at, b", LexState::Insert, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+    }
+
+    #[test]
+    fn test_update_ruleset() {
+        let r = lex_one("UPDATE t SET a = 1", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "update"
+                    }
+                }
+            }
+        ));
+
+        let r = lex_one("set a = 1", LexState::Update, &polyglot()).unwrap();
+        assert!(matches!(r.action, Action::HandleReservedKeyword { .. }));
+        assert_eq!(r.token_text, "set");
+
+        let r = lex_one("set a = 1", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+    }
+
+    #[test]
+    fn test_merge_ruleset() {
+        let r = lex_one(
+            "MERGE INTO t USING s ON t.id = s.id",
+            LexState::Main,
+            &polyglot(),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "MERGE INTO");
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "merge"
+                    }
+                }
+            }
+        ));
+
+        let r = lex_one(
+            "when not matched by source then delete",
+            LexState::Merge,
+            &polyglot(),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "when not matched by source");
+        let r = lex_one("then update set a = s.a", LexState::Merge, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "then update set");
+
+        let r = lex_one("on t.id = s.id", LexState::Merge, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleTopLevelOrNestedKeyword { .. }
+            }
+        ));
+
+        // CASE ... WHEN ... THEN inside a MERGE still lexes as usual
+        let r = lex_one("then t.v else s.v end", LexState::Merge, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "then");
+
+        let r = lex_one("merged_at = s.merged_at", LexState::Merge, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+    }
+
+    #[test]
+    fn test_create_table_ruleset() {
+        let r = lex_one(
+            "CREATE OR REPLACE TABLE t (a int)",
+            LexState::Main,
+            &polyglot(),
+        )
+        .unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "create_table"
+                    }
+                }
+            }
+        ));
+        // Without a column list, CREATE TABLE is still unsupported
+        let r = lex_one("create table t as select 1", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "unsupported"
+                    }
+                }
+            }
+        ));
+
+        let r = lex_one(
+            "create temporary table if not exists t (a int)",
+            LexState::CreateTable,
+            &polyglot(),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "create temporary table if not exists");
+
+        let r = lex_one("NOT NULL, b int", LexState::CreateTable, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "NOT NULL");
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::AddNode {
+                    token_type: TokenType::DdlKeyword
+                }
+            }
+        ));
+        let r = lex_one("primary key (a, b)", LexState::CreateTable, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "primary key");
+
+        // Column names are not keywords
+        let r = lex_one("created_at date", LexState::CreateTable, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+        let r = lex_one("date date, b int", LexState::CreateTable, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "date");
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+
+        // Column types take in their precision and scale
+        let is_data_type = |r: &LexResult| {
+            matches!(
+                r.action,
+                Action::HandleReservedKeyword {
+                    inner: Action::AddNode {
+                        token_type: TokenType::DataType
+                    }
+                }
+            )
+        };
+        let r = lex_one(
+            "decimal(12, 2) default 0",
+            LexState::CreateTable,
+            &polyglot(),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "decimal(12, 2)");
+        assert!(is_data_type(&r));
+        let r = lex_one("VARCHAR2 (10 BYTE),", LexState::CreateTable, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "VARCHAR2 (10 BYTE)");
+        let r = lex_one("date not null", LexState::CreateTable, &polyglot()).unwrap();
+        assert!(is_data_type(&r));
+        // a referenced column list is not a type's parameters
+        let r = lex_one("customers (id)", LexState::CreateTable, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "customers");
+    }
+
+    #[test]
+    fn test_block_ruleset() {
+        let scripting = Syntax {
+            scripting_blocks: true,
+            ..polyglot()
+        };
+        let r = lex_one("BEGIN\n  SELECT 1;\nEND;", LexState::Main, &scripting).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleStatementStartKeyword {
+                    statement_start: Action::LexRuleset {
+                        ruleset_name: "block"
+                    },
+                    ..
+                }
+            }
+        ));
+
+        // Transactions and single declarations are not blocks
+        let r = lex_one("begin transaction;", LexState::Main, &scripting).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "unsupported"
+                    }
+                }
+            }
+        ));
+        let r = lex_one("declare x int64 default 0;", LexState::Main, &scripting).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::AddNode {
+                        token_type: TokenType::UntermKeyword
+                    }
+                }
+            }
+        ));
+        // Snowflake Scripting declarations are a section, even on one line
+        let snowflake = Syntax {
+            declare_sections: true,
+            ..scripting.clone()
+        };
+        let r = lex_one("declare x int; begin", LexState::Main, &snowflake).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleStatementStartKeyword {
+                    statement_start: Action::LexRuleset {
+                        ruleset_name: "block"
+                    },
+                    ..
+                }
+            }
+        ));
+        // Other dialects have no procedural blocks
+        let r = lex_one("if x > 0 then", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+
+        let r = lex_one("end if;", LexState::Block, &scripting).unwrap();
+        assert_eq!(r.token_text, "end if");
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleBlockEnd
+            }
+        ));
+
+        // THEN / ELSE / END also close CASE expressions inside a block
+        let r = lex_one("then 1 else 2 end", LexState::Block, &scripting).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleTopLevelOrNestedKeyword { .. }
+            }
+        ));
+
+        let r = lex_one("ending_balance = 0", LexState::Block, &scripting).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+    }
+
+    #[test]
+    fn test_dialect_keywords() {
+        static NO_KEYWORDS: Keywords = Keywords {
+            unterm_keywords: &[],
+            word_operators: &[],
+            set_operators: &[],
+            reserved_words: &[],
+            custom: None,
+        };
+
+        let r = lex_one("prewhere a = 1", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::AddNode {
+                    token_type: TokenType::UntermKeyword
+                }
+            }
+        ));
+        let r = lex_one(
+            "prewhere a = 1",
+            LexState::Main,
+            &Syntax::new(NO_KEYWORDS.clone()),
+        )
+        .unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+
+        // Phrases from other dialects are lexed one word at a time
+        let r = lex_one("global join t", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "global join");
+        let r = lex_one(
+            "global join t",
+            LexState::Main,
+            &Syntax::new(NO_KEYWORDS.clone()),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "global");
+        let r = lex_one(
+            "lateral view explode(a)",
+            LexState::Main,
+            &Syntax::new(NO_KEYWORDS.clone()),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "lateral");
+        let r = lex_one(
+            "union all by name",
+            LexState::Main,
+            &Syntax::new(NO_KEYWORDS.clone()),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "union all");
+        let r = lex_one(
+            "union\n  all by name",
+            LexState::Main,
+            &Syntax::new(NO_KEYWORDS.clone()),
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "union\n  all");
+        let r = lex_one(
+            "put file://a @s",
+            LexState::Main,
+            &Syntax::new(NO_KEYWORDS.clone()),
+        )
+        .unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+    }
+
+    #[test]
+    fn test_snowflake_syntax() {
+        let snowflake = Syntax {
+            stage_references: true,
+            dollar_names: true,
+            flow_operator: true,
+            stage_statements: true,
+            ..polyglot()
+        };
+
+        let r = lex_one("@my_stage/path/file.csv.gz;", LexState::Main, &snowflake).unwrap();
+        assert_eq!(r.token_text, "@my_stage/path/file.csv.gz");
+        let r = lex_one("@~/staged", LexState::Main, &snowflake).unwrap();
+        assert_eq!(r.token_text, "@~/staged");
+        let r = lex_one("@%my_table ", LexState::Main, &snowflake).unwrap();
+        assert_eq!(r.token_text, "@%my_table");
+        let r = lex_one("@my_stage/path/", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "@my_stage");
+
+        let r = lex_one("metadata$filename", LexState::Main, &snowflake).unwrap();
+        assert_eq!(r.token_text, "metadata$filename");
+        let r = lex_one("metadata$filename", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "metadata");
+
+        // MATCH_RECOGNIZE clauses are names outside of Snowflake
+        let r = lex_one("measures, define", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+        static MATCH_RECOGNIZE_KEYWORDS: Keywords = Keywords {
+            unterm_keywords: &[&SNOWFLAKE_UNTERM_KEYWORDS],
+            word_operators: &[],
+            set_operators: &[],
+            reserved_words: &[],
+            custom: None,
+        };
+        let match_recognize = Syntax::new(MATCH_RECOGNIZE_KEYWORDS.clone());
+        let r = lex_one("measures, define", LexState::Main, &match_recognize).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleMatchRecognizeKeyword { .. }
+            }
+        ));
+
+        let r = lex_one("->> select 1", LexState::Main, &snowflake).unwrap();
+        assert!(matches!(r.action, Action::HandleFlowOperator));
+        let r = lex_one("->> 'id'", LexState::Main, &polyglot()).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Operator
+            }
+        ));
+        let r = lex_one("tables ->> select 1", LexState::Unsupported, &snowflake).unwrap();
+        assert_eq!(r.token_text, "tables");
+
+        let r = lex_one("copy into t from @s", LexState::Main, &snowflake).unwrap();
+        assert_eq!(r.token_text, "copy into");
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "stage"
+                    }
+                }
+            }
+        ));
+        let r = lex_one("create stage s", LexState::Main, &snowflake).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleNonreservedTopLevelKeyword {
+                    inner: Action::LexRuleset {
+                        ruleset_name: "stage"
+                    }
+                }
+            }
+        ));
+        let r = lex_one("file_format = (type = csv)", LexState::Stage, &snowflake).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleTopLevelOrNestedKeyword { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_postgres_syntax() {
+        let postgres = Syntax {
+            postgres_operators: true,
+            standard_strings: true,
+            ..polyglot()
+        };
+
+        for (src, op) in [
+            ("~~* 'a%'", "~~*"),
+            ("!~~ 'a%'", "!~~"),
+            (">>= b", ">>="),
+            ("^@ 'ab'", "^@"),
+            ("#>> '{a}'", "#>>"),
+            ("||/ 27", "||/"),
+            ("# b", "#"),
+            ("=-1", "="),
+            ("*-- c", "*"),
+        ] {
+            let r = lex_one(src, LexState::Main, &postgres).unwrap();
+            assert_eq!(r.token_text, op, "{}", src);
+        }
+        let hash_comments = Syntax {
+            hash_comments: true,
+            ..polyglot()
+        };
+        let r = lex_one("# comment", LexState::Main, &hash_comments).unwrap();
+        assert!(matches!(r.action, Action::AddComment));
+        let r = lex_one("# comment", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "#");
+
+        let r = lex_one(r"'C:\' as p", LexState::Main, &postgres).unwrap();
+        assert_eq!(r.token_text, r"'C:\'");
+        let r = lex_one(r"E'it\'s' as e", LexState::Main, &postgres).unwrap();
+        assert_eq!(r.token_text, r"E'it\'s'");
+        let r = lex_one(r"U&'d\0061t' as u", LexState::Main, &postgres).unwrap();
+        assert_eq!(r.token_text, r"U&'d\0061t'");
+
+        let r = lex_one("with time zone", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "with time zone");
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+    }
+
+    #[test]
+    fn test_databricks_syntax() {
+        let databricks = Syntax {
+            widget_references: true,
+            ..polyglot()
+        };
+
+        let r = lex_one("${catalog}.sales", LexState::Main, &databricks).unwrap();
+        assert_eq!(r.token_text, "${catalog}");
+        let r = lex_one("${catalog}.sales", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "$");
+
+        let r = lex_one("zorder by (a)", LexState::Main, &databricks).unwrap();
+        assert_eq!(r.token_text, "zorder by");
+        // OPTIMIZE only starts a statement
+        let r = lex_one("optimize = 1", LexState::Main, &databricks).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleStatementStartKeyword { .. }
+            }
+        ));
+        let r = lex_one(
+            "merge with schema evolution into t",
+            LexState::Main,
+            &databricks,
+        )
+        .unwrap();
+        assert_eq!(r.token_text, "merge with schema evolution into");
+        assert!(scan_rest_for_create_table(
+            b" table t using delta as select 1"
+        ));
+        assert!(!scan_rest_for_create_table(b" table t as select 1"));
+    }
+
+    #[test]
+    fn test_bigquery_syntax() {
+        let bigquery = Syntax {
+            string_prefixes: true,
+            double_quoted_strings: true,
+            ..polyglot()
+        };
+
+        for src in [r"r'\d+'", r#"B"\x00""#, "rb'''a'b'''", r"BR'\'x'"] {
+            let r = lex_one(src, LexState::Main, &bigquery).unwrap();
+            assert_eq!(r.token_text, src);
+        }
+        let r = lex_one("rr'a'", LexState::Main, &bigquery).unwrap();
+        assert_eq!(r.token_text, "rr");
+        let r = lex_one("b'a'", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "b");
+
+        let r = lex_one(r#""it's""#, LexState::Main, &bigquery).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::Name
+            }
+        ));
+        let r = lex_one("`my-project.dataset.Table`", LexState::Main, &bigquery).unwrap();
+        assert_eq!(r.token_text, "`my-project.dataset.Table`");
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::QuotedName
+            }
+        ));
+    }
+
+    #[test]
+    fn test_tsql_syntax() {
+        let tsql = Syntax {
+            bracket_identifiers: true,
+            system_variables: true,
+            batch_separator: true,
+            select_top: true,
+            tsql_blocks: true,
+            ..polyglot()
+        };
+
+        let r = lex_one("[Order]]s].[id]", LexState::Main, &tsql).unwrap();
+        assert_eq!(r.token_text, "[Order]]s]");
+        assert!(matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::QuotedName
+            }
+        ));
+        let r = lex_one("[1]", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "[");
+
+        let r = lex_one("@@rowcount > 0", LexState::Main, &tsql).unwrap();
+        assert_eq!(r.token_text, "@@rowcount");
+        let r = lex_one("@@ to_tsquery('a')", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "@@");
+
+        let r = lex_one("GO 2\nselect 1", LexState::Main, &tsql).unwrap();
+        assert_eq!(r.token_text, "GO 2");
+        assert!(matches!(r.action, Action::HandleSemicolon));
+        let r = lex_one("go_live from t", LexState::Main, &tsql).unwrap();
+        assert_eq!(r.token_text, "go_live");
+        let r = lex_one("go\n", LexState::Main, &polyglot()).unwrap();
+        assert!(!matches!(r.action, Action::HandleSemicolon));
+
+        let r = lex_one("select top (@n) a", LexState::Main, &tsql).unwrap();
+        assert_eq!(r.token_text, "select top (@n)");
+        let r = lex_one("select top 5 percent with ties a", LexState::Main, &tsql).unwrap();
+        assert_eq!(r.token_text, "select top 5 percent with ties");
+        let r = lex_one("select top 5 percent", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "select top 5");
+        let r = lex_one("commit tran", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "commit");
+
+        let r = lex_one("end if @x > 0", LexState::Block, &tsql).unwrap();
+        assert_eq!(r.token_text, "end");
+        let r = lex_one("begin tran", LexState::Block, &tsql).unwrap();
+        assert_eq!(r.token_text, "begin tran");
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleBlockStart
+            }
+        ));
+    }
+
+    #[test]
+    fn test_mysql_syntax() {
+        let mysql = Syntax {
+            hash_comments: true,
+            delimiter_statements: true,
+            limit_offset_count: true,
+            ..polyglot()
+        };
+        let no_hash_comments = polyglot();
+
+        let r = lex_one("# note\nselect", LexState::Main, &mysql).unwrap();
+        assert!(matches!(r.action, Action::AddComment));
+        let r = lex_one("#1, a", LexState::Main, &no_hash_comments).unwrap();
+        assert_eq!(r.token_text, "#1");
+        let r = lex_one("##temp", LexState::Main, &no_hash_comments).unwrap();
+        assert_eq!(r.token_text, "##temp");
+        let r = lex_one("# 2", LexState::Main, &no_hash_comments).unwrap();
+        assert_eq!(r.token_text, "#");
+        let r = lex_one("# fmt: on", LexState::FmtOff, &no_hash_comments).unwrap();
+        assert!(!matches!(
+            r.action,
+            Action::AddNode {
+                token_type: TokenType::FmtOn
+            }
+        ));
+
+        let r = lex_one("DELIMITER //\n", LexState::Main, &mysql).unwrap();
+        assert_eq!(r.token_text, "DELIMITER //");
+        assert!(matches!(r.action, Action::HandleDelimiter));
+        let r = lex_one("delimiter from t", LexState::Main, &mysql).unwrap();
+        assert_eq!(r.token_text, "delimiter");
+
+        let r = lex_one("limit 10, 20", LexState::Main, &mysql).unwrap();
+        assert_eq!(r.token_text, "limit 10, 20");
+        let r = lex_one("limit 10, a", LexState::Main, &mysql).unwrap();
+        assert_eq!(r.token_text, "limit");
+        let r = lex_one("limit 10, 20", LexState::Main, &polyglot()).unwrap();
+        assert_eq!(r.token_text, "limit");
+        let r = lex_one("select straight_join a", LexState::Main, &mysql).unwrap();
+        assert_eq!(r.token_text, "select straight_join");
+        let r = lex_one("on duplicate key update a = 1", LexState::Insert, &mysql).unwrap();
+        assert_eq!(r.token_text, "on duplicate key update");
+        let r = lex_one("values(a)", LexState::Update, &mysql).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::HandleTopLevelOrNestedKeyword { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_custom_keywords() {
+        let words = |ws: &[&str]| ws.iter().map(|w| w.to_string()).collect();
+        let syntax = Syntax::new(Keywords {
+            custom: Some(Arc::new(CustomKeywords {
+                unterm_keywords: words(&["sample by", "settle"]),
+                word_operators: words(&["overlaps"]),
+                set_operators: words(&["union distinct by name"]),
+                reserved_words: words(&["vacuum"]),
+            })),
+            ..ALL_DIALECT_KEYWORDS.clone()
+        });
+
+        let r = lex_one("SAMPLE  BY 10", LexState::Main, &syntax).unwrap();
+        assert_eq!(r.token_text, "SAMPLE  BY");
+        let r = lex_one("sample_by", LexState::Main, &syntax).unwrap();
+        assert_eq!(r.token_text, "sample_by");
+        let r = lex_one("settle a", LexState::Main, &syntax).unwrap();
+        assert_eq!(r.token_text, "settle");
+        let r = lex_one("union distinct by name select", LexState::Main, &syntax).unwrap();
+        assert_eq!(r.token_text, "union distinct by name");
+        let r = lex_one("overlaps b", LexState::Main, &syntax).unwrap();
+        assert!(matches!(
+            r.action,
+            Action::HandleReservedKeyword {
+                inner: Action::AddNode {
+                    token_type: TokenType::WordOperator
+                }
+            }
+        ));
+        assert!(syntax.keywords.is_reserved_word("vacuum"));
+        assert!(!Syntax::new(ALL_DIALECT_KEYWORDS.clone())
+            .keywords
+            .is_unterm_keyword("settle"));
+    }
 }
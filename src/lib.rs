@@ -5,6 +5,7 @@ pub(crate) mod comment;
 pub(crate) mod config;
 pub(crate) mod dialect;
 pub mod error;
+pub(crate) mod explicit_aliases;
 pub(crate) mod formatter;
 pub(crate) mod jinja_formatter;
 pub(crate) mod lexer;
@@ -15,6 +16,7 @@ pub(crate) mod node;
 pub(crate) mod node_manager;
 pub(crate) mod operator_precedence;
 pub(crate) mod query;
+pub(crate) mod redundant_parens;
 pub mod report;
 pub(crate) mod segment;
 pub(crate) mod splitter;
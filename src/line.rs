@@ -1,6 +1,7 @@
 use std::sync::LazyLock;
 
 use crate::comment::Comment;
+use crate::mode::IndentStyle;
 use crate::node::{Node, NodeIndex};
 use crate::token::TokenType;
 
@@ -28,6 +29,61 @@ pub fn indent_str(n: usize) -> &'static str {
     }
 }
 
+/// Pre-computed strings of 0..=50 tabs, one per nesting level.
+static TAB_CACHE: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    (0..=50)
+        .map(|n| &*Box::leak("\t".repeat(n).into_boxed_str()))
+        .collect()
+});
+
+/// Return a cached `&'static str` of `n` tabs, like `indent_str`.
+pub fn tab_str(n: usize) -> &'static str {
+    if n <= 50 {
+        TAB_CACHE[n]
+    } else {
+        &*Box::leak("\t".repeat(n).into_boxed_str())
+    }
+}
+
+/// How one level of indentation is rendered and measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Indent {
+    /// Columns per level. Tabs count as this many columns toward the
+    /// line length.
+    pub width: usize,
+    pub style: IndentStyle,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Self {
+            width: 4,
+            style: IndentStyle::Spaces,
+        }
+    }
+}
+
+impl Indent {
+    /// Number of columns taken by `levels` levels of indentation.
+    pub fn columns(&self, levels: usize) -> usize {
+        self.width * levels
+    }
+
+    /// Indentation prefix for `levels` levels.
+    pub fn prefix(&self, levels: usize) -> &'static str {
+        match self.style {
+            IndentStyle::Spaces => indent_str(self.columns(levels)),
+            IndentStyle::Tabs => tab_str(levels),
+        }
+    }
+
+    /// Display width of `s`, counting each tab as `width` columns.
+    pub fn display_width(&self, s: &str) -> usize {
+        let tabs = s.bytes().filter(|&b| b == b'\t').count();
+        s.len() - tabs + tabs * self.width
+    }
+}
+
 /// A Line is a collection of Nodes intended to be printed on one line,
 /// plus any attached comments.
 #[derive(Debug, Clone)]
@@ -36,6 +92,9 @@ pub struct Line {
     pub nodes: Vec<NodeIndex>,
     pub comments: Vec<Comment>,
     pub formatting_disabled: bool,
+    /// Extra spaces before inline comments, to line them up with the
+    /// comments on neighbouring lines.
+    pub comment_padding: usize,
 }
 
 impl Line {
@@ -45,6 +104,7 @@ impl Line {
             nodes: Vec::with_capacity(4),
             comments: Vec::new(),
             formatting_disabled: false,
+            comment_padding: 0,
         }
     }
 
@@ -72,26 +132,31 @@ impl Line {
             .unwrap_or((0, 0))
     }
 
-    /// Number of spaces for indentation: 4 per SQL depth + 4 per Jinja depth.
-    pub fn indent_size(&self, arena: &[Node]) -> usize {
+    /// Indentation levels: one per SQL depth + one per Jinja depth.
+    pub fn indent_level(&self, arena: &[Node]) -> usize {
         let (sql, jinja) = self.depth(arena);
-        4 * (sql + jinja)
+        sql + jinja
+    }
+
+    /// Number of columns of indentation.
+    pub fn indent_size(&self, arena: &[Node], indent: Indent) -> usize {
+        indent.columns(self.indent_level(arena))
     }
 
     /// Indentation prefix string. Returns a cached `&str` for common sizes.
-    pub fn indentation<'a>(&self, arena: &[Node]) -> &'a str {
-        indent_str(self.indent_size(arena))
+    pub fn indentation<'a>(&self, arena: &[Node], indent: Indent) -> &'a str {
+        indent.prefix(self.indent_level(arena))
     }
 
     /// Render the line to a string (nodes only, no standalone comments).
-    pub fn render(&self, arena: &[Node]) -> String {
+    pub fn render(&self, arena: &[Node], indent: Indent) -> String {
         if self.is_blank_line(arena) {
             return "\n".to_string();
         }
         if self.has_formatting_disabled() {
             return self.render_formatting_disabled(arena);
         }
-        let mut result = String::with_capacity(self.indent_size(arena) + 80);
+        let mut result = String::with_capacity(self.indent_size(arena, indent) + 80);
         let mut first_content = true;
         for &idx in &self.nodes {
             let node = &arena[idx];
@@ -99,7 +164,7 @@ impl Line {
                 continue;
             }
             if first_content {
-                result.push_str(self.indentation(arena));
+                result.push_str(self.indentation(arena, indent));
                 result.push_str(&node.value);
                 first_content = false;
             } else {
@@ -137,26 +202,27 @@ impl Line {
     pub fn render_with_comments(
         &self,
         arena: &[Node],
+        indent: Indent,
         max_line_length: usize,
         indent_override: Option<&str>,
     ) -> String {
         if self.comments.is_empty() {
-            return self.render(arena);
+            return self.render(arena, indent);
         }
 
         let mut result = String::new();
-        let own_prefix = self.indentation(arena);
+        let own_prefix = self.indentation(arena, indent);
         let prefix = indent_override.unwrap_or(own_prefix);
 
         // A multiline comment that is not standalone (e.g., /* ... */ appearing mid-line)
         // must still be rendered as standalone to avoid being silently dropped.
         for comment in &self.comments {
             if comment.is_standalone || comment.is_multiline() {
-                result.push_str(&comment.render_standalone(prefix, max_line_length));
+                result.push_str(&comment.render_standalone(prefix, indent, max_line_length));
             }
         }
 
-        let base = self.render(arena);
+        let base = self.render(arena, indent);
         let has_only_newline_node = self.nodes.len() == 1 && arena[self.nodes[0]].is_newline();
         if has_only_newline_node && !result.is_empty() {
             return result;
@@ -169,6 +235,7 @@ impl Line {
         } else {
             let trimmed = base.trim_end_matches('\n');
             result.push_str(trimmed);
+            result.push_str(indent_str(self.comment_padding));
             for c in inline_comments {
                 result.push_str(&c.render_inline());
             }
@@ -180,7 +247,8 @@ impl Line {
 
     /// Length of the rendered line (longest sub-line if multiline Jinja).
     /// Computed arithmetically for the common case to avoid allocating a String.
-    pub fn len(&self, arena: &[Node]) -> usize {
+    /// Tabs count as `indent.width` columns.
+    pub fn len(&self, arena: &[Node], indent: Indent) -> usize {
         if self.is_blank_line(arena) {
             return 1;
         }
@@ -188,7 +256,7 @@ impl Line {
             return self
                 .render_formatting_disabled(arena)
                 .lines()
-                .map(|l| l.len())
+                .map(|l| indent.display_width(l))
                 .max()
                 .unwrap_or(0);
         }
@@ -201,14 +269,14 @@ impl Line {
             .any(|&idx| !arena[idx].is_newline() && arena[idx].value.contains('\n'));
         if has_multiline_node {
             return self
-                .render(arena)
+                .render(arena, indent)
                 .lines()
-                .map(|l| l.len())
+                .map(|l| indent.display_width(l))
                 .max()
                 .unwrap_or(0);
         }
         // Fast path: compute length arithmetically
-        let mut length = self.indent_size(arena);
+        let mut length = self.indent_size(arena, indent);
         let mut first_content = true;
         for &idx in &self.nodes {
             let node = &arena[idx];
@@ -226,15 +294,18 @@ impl Line {
     }
 
     /// Length including inline comments (for merger length checks).
-    pub fn len_with_comments(&self, arena: &[Node]) -> usize {
-        let base = self.len(arena);
+    pub fn len_with_comments(&self, arena: &[Node], indent: Indent) -> usize {
+        let base = self.len(arena, indent);
         let inline_len: usize = self
             .comments
             .iter()
             .filter(|c| c.is_inline())
             .map(|c| c.render_inline().len())
             .sum();
-        base + inline_len
+        if inline_len == 0 {
+            return base;
+        }
+        base + self.comment_padding + inline_len
     }
 
     /// True if the line has no nodes.
@@ -390,6 +461,28 @@ impl Line {
         false
     }
 
+    /// True if this line begins the body of a procedural block, i.e. the
+    /// token preceding it is `then`, `do`, `begin`, `else`, etc., or the
+    /// statement after a T-SQL `if`, `else` or `while` condition.
+    pub fn starts_block_body(&self, arena: &[Node]) -> bool {
+        if self
+            .first_content_node(arena)
+            .is_some_and(|n| n.starts_condition_body)
+        {
+            return true;
+        }
+        let mut idx = self.previous_node;
+        while let Some(i) = idx {
+            let node = &arena[i];
+            if node.token.token_type.does_not_set_prev_sql_context() {
+                idx = node.previous_node;
+            } else {
+                return node.is_opening_block() && !node.is_block_condition_keyword();
+            }
+        }
+        false
+    }
+
     /// True if this closes a simple jinja block from a previous line.
     pub fn closes_simple_jinja_block(&self, arena: &[Node]) -> bool {
         self.first_content_node(arena)
@@ -419,6 +512,11 @@ impl Line {
             }
         }
 
+        // The body of a procedural block is never merged into its header
+        if self.starts_block_body(arena) {
+            return true;
+        }
+
         let depth = self.depth(arena);
         if depth <= prev_segment_depth || depth.1 < prev_segment_depth.1 {
             if (self.closes_bracket_from_previous_line(arena)
@@ -501,7 +599,7 @@ mod tests {
         let mut line = Line::new(None);
         line.append_node(idx);
         assert_eq!(line.depth(&arena), (1, 0));
-        assert_eq!(line.indent_size(&arena), 4);
+        assert_eq!(line.indent_size(&arena, Indent::default()), 4);
     }
 
     #[test]
@@ -515,7 +613,7 @@ mod tests {
         line.nodes.push(select_idx);
         line.nodes.push(name_idx);
 
-        let rendered = line.render(&arena);
+        let rendered = line.render(&arena, Indent::default());
         assert_eq!(rendered, "select a\n");
     }
 
@@ -559,7 +657,7 @@ mod tests {
         let mut line = Line::new(None);
         line.append_node(idx);
         line.append_node(nl_idx);
-        assert!(line.len(&arena) > 88);
+        assert!(line.len(&arena, Indent::default()) > 88);
     }
 
     #[test]
@@ -675,7 +773,7 @@ mod tests {
         let nl_idx = make_node_in_arena(&mut arena, TokenType::Newline, "\n", "");
         let mut line = Line::new(None);
         line.append_node(nl_idx);
-        assert_eq!(line.render(&arena), "\n");
+        assert_eq!(line.render(&arena, Indent::default()), "\n");
     }
 
     #[test]
@@ -685,7 +783,31 @@ mod tests {
         arena[idx].bracket_depth = 1; // depth 1
         let mut line = Line::new(None);
         line.append_node(idx);
-        assert_eq!(line.indentation(&arena), "    "); // 4 spaces per depth level
+        assert_eq!(line.indentation(&arena, Indent::default()), "    "); // 4 spaces per depth level
+    }
+
+    #[test]
+    fn test_indent_width_and_tabs() {
+        let mut arena = Vec::new();
+        let idx = make_node_in_arena(&mut arena, TokenType::Name, "a", "");
+        arena[idx].bracket_depth = 2;
+        let mut line = Line::new(None);
+        line.append_node(idx);
+
+        let two = Indent {
+            width: 2,
+            style: IndentStyle::Spaces,
+        };
+        assert_eq!(line.render(&arena, two), "    a\n");
+        assert_eq!(line.len(&arena, two), 5);
+
+        let tabs = Indent {
+            width: 8,
+            style: IndentStyle::Tabs,
+        };
+        assert_eq!(line.render(&arena, tabs), "\t\ta\n");
+        assert_eq!(line.len(&arena, tabs), 17);
+        assert_eq!(tabs.display_width("\tab"), 10);
     }
 
     #[test]
@@ -725,7 +847,7 @@ mod tests {
         );
         line.append_comment(comment);
 
-        let rendered = line.render_with_comments(&arena, 88, None);
+        let rendered = line.render_with_comments(&arena, Indent::default(), 88, None);
         assert!(rendered.contains("a"));
         assert!(rendered.contains("inline comment"));
     }
@@ -747,7 +869,7 @@ mod tests {
         );
         line.append_comment(comment);
 
-        let rendered = line.render_with_comments(&arena, 88, None);
+        let rendered = line.render_with_comments(&arena, Indent::default(), 88, None);
         assert!(rendered.contains("standalone"));
         assert!(rendered.contains("a"));
     }
@@ -18,9 +18,9 @@ struct Cli {
     #[arg(short = 'l', long, default_value_t = 88)]
     line_length: usize,
 
-    /// SQL dialect: polyglot, duckdb, clickhouse.
-    #[arg(short = 'd', long, default_value = "polyglot")]
-    dialect: String,
+    /// SQL dialect: polyglot, duckdb, clickhouse, snowflake, postgres, bigquery, databricks, trino, tsql, mysql, or one defined in the config file [default: polyglot].
+    #[arg(short = 'd', long)]
+    dialect: Option<String>,
 
     /// Check formatting without writing changes.
     #[arg(long)]
@@ -116,7 +116,7 @@ fn main() {
 
     let mode = Mode {
         line_length: cli.line_length,
-        dialect_name: cli.dialect,
+        dialect_name: cli.dialect.unwrap_or(base_mode.dialect_name),
         check: cli.check,
         diff: cli.diff,
         fast: cli.fast || env_fast,
@@ -139,6 +139,19 @@ fn main() {
         },
         single_process: cli.single_process,
         reset_cache: cli.reset_cache,
+        dialects: base_mode.dialects,
+        case_sensitive_names: base_mode.case_sensitive_names,
+        keyword_case: base_mode.keyword_case,
+        identifier_case: base_mode.identifier_case,
+        function_case: base_mode.function_case,
+        comma_style: base_mode.comma_style,
+        indent_width: base_mode.indent_width,
+        indent_style: base_mode.indent_style,
+        align_aliases: base_mode.align_aliases,
+        explicit_aliases: base_mode.explicit_aliases,
+        normalize_operators: base_mode.normalize_operators,
+        single_quote_strings: base_mode.single_quote_strings,
+        remove_redundant_parens: base_mode.remove_redundant_parens,
     };
 
     // Build the tokio runtime, capping both async workers and blocking threads.
@@ -1,5 +1,5 @@
 use crate::error::ControlFlow;
-use crate::line::Line;
+use crate::line::{Indent, Line};
 use crate::node::Node;
 use crate::operator_precedence::OperatorPrecedence;
 use crate::segment::{build_segments, Segment};
@@ -14,11 +14,32 @@ use crate::segment::{build_segments, Segment};
 /// - Recursive: segments are built, merged, then recursed into
 pub struct LineMerger {
     max_length: usize,
+    indent: Indent,
+    /// Whether `:=` is an assignment that binds like `=` (MySQL).
+    assignment_operator: bool,
 }
 
 impl LineMerger {
-    pub fn new(max_length: usize) -> Self {
-        Self { max_length }
+    pub fn new(max_length: usize, indent: Indent) -> Self {
+        Self {
+            max_length,
+            indent,
+            assignment_operator: false,
+        }
+    }
+
+    /// Treat `:=` as an assignment that binds like `=`.
+    pub fn with_assignment_operator(mut self, assignment_operator: bool) -> Self {
+        self.assignment_operator = assignment_operator;
+        self
+    }
+
+    fn precedence(&self, node: &Node, arena: &[Node]) -> OperatorPrecedence {
+        if self.assignment_operator {
+            OperatorPrecedence::from_node_with_assignment(node, arena)
+        } else {
+            OperatorPrecedence::from_node(node, arena)
+        }
     }
 
     /// Main entry: try to merge lines.
@@ -248,7 +269,7 @@ impl LineMerger {
         }
         merged_line.comments = comments;
 
-        if merged_line.len(arena) > self.max_length {
+        if merged_line.len(arena, self.indent) > self.max_length {
             return Err(ControlFlow::CannotMerge);
         }
 
@@ -347,6 +368,19 @@ impl LineMerger {
                 }
             }
 
+            // A block's body never joins its BEGIN, and END never joins the body
+            if !nodes.is_empty()
+                && (line.starts_block_body(arena)
+                    || line.first_content_node(arena).is_some_and(|n| {
+                        matches!(
+                            n.token.token_type,
+                            crate::token::TokenType::BlockStart | crate::token::TokenType::BlockEnd
+                        )
+                    }))
+            {
+                return Err(ControlFlow::CannotMerge);
+            }
+
             if !nodes.is_empty() {
                 if let Some(first) = line.first_content_node(arena) {
                     if first.token.token_type == crate::token::TokenType::JinjaBlockEnd
@@ -520,6 +554,20 @@ impl LineMerger {
         if first.token.token_type == crate::token::TokenType::UntermKeyword
             && first.value.eq_ignore_ascii_case("using")
         {
+            // MERGE INTO ... USING (subquery) keeps USING as its own clause
+            let follows_merge = prev_segment
+                .head(arena)
+                .ok()
+                .and_then(|(_, prev_line)| prev_line.first_content_node(arena))
+                .is_some_and(|n| {
+                    n.token.token_type == crate::token::TokenType::UntermKeyword
+                        && n.value
+                            .get(..5)
+                            .is_some_and(|p| p.eq_ignore_ascii_case("merge"))
+                });
+            if follows_merge {
+                return false;
+            }
             return segment.lines.iter().any(|l| {
                 l.nodes
                     .iter()
@@ -599,7 +647,7 @@ impl LineMerger {
                             // Exclude ON from operator sequences — it's handled
                             // separately by Phase 3 stubborn merge
                             && n.token.token_type != crate::token::TokenType::On
-                            && OperatorPrecedence::from_node(n, arena) <= max_precedence
+                            && self.precedence(n, arena) <= max_precedence
                     })
                     .unwrap_or(false);
                 starts_with_op || starts_with_comma
@@ -862,7 +910,7 @@ mod tests {
     fn test_no_merge_single_line() {
         let mut arena = Vec::new();
         let line = make_simple_line(&mut arena, TokenType::Name, "a");
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[line], &arena);
         assert_eq!(result.len(), 1);
     }
@@ -873,7 +921,7 @@ mod tests {
         let line1 = make_simple_line(&mut arena, TokenType::Name, "a");
         let line2 = make_simple_line(&mut arena, TokenType::Name, "b");
 
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[line1, line2], &arena);
         // Should merge
         assert!(result.len() <= 2);
@@ -886,7 +934,7 @@ mod tests {
         let line1 = make_simple_line(&mut arena, TokenType::Name, &long_val);
         let line2 = make_simple_line(&mut arena, TokenType::Name, &long_val);
 
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[line1, line2], &arena);
         // Should NOT merge since combined length > 88
         assert!(
@@ -898,7 +946,7 @@ mod tests {
     #[test]
     fn test_merge_empty_input() {
         let arena = Vec::new();
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[], &arena);
         assert!(result.is_empty());
     }
@@ -917,7 +965,7 @@ mod tests {
 
         let line3 = make_simple_line(&mut arena, TokenType::Name, "b");
 
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[line1, semi_line, line3], &arena);
         // Should not merge across the semicolon
         assert!(
@@ -940,7 +988,7 @@ mod tests {
         disabled_line.append_node(nl);
         disabled_line.formatting_disabled = true;
 
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[disabled_line], &arena);
         assert_eq!(result.len(), 1);
     }
@@ -957,7 +1005,7 @@ mod tests {
 
         let line3 = make_simple_line(&mut arena, TokenType::Name, "b");
 
-        let merger = LineMerger::new(88);
+        let merger = LineMerger::new(88, Indent::default());
         let result = merger.maybe_merge_lines(&[line1, blank, line3], &arena);
         // Should produce at least 1 line and not crash
         assert!(
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
-use crate::dialect::{self, Dialect};
+use crate::dialect::{self, CustomDialect, Dialect};
 use crate::error::SqlfmtError;
+use crate::line::Indent;
 
 /// Mode holds all formatting configuration for sqlfmt.
 #[derive(Debug, Clone, Deserialize)]
@@ -57,11 +60,160 @@ pub struct Mode {
 
     #[serde(default)]
     pub reset_cache: bool,
+
+    /// User-defined dialects from `[tool.sqlfmt.dialects.<name>]`, by
+    /// lowercased name.
+    #[serde(default)]
+    pub dialects: HashMap<String, DialectConfig>,
+
+    /// Keep (or lowercase) the case of names regardless of the dialect.
+    /// None follows the dialect, which lowercases names unless they are
+    /// case-sensitive there, as in ClickHouse.
+    #[serde(default)]
+    pub case_sensitive_names: Option<bool>,
+
+    /// Case of keywords, operators spelled as words and statement starts.
+    #[serde(default)]
+    pub keyword_case: Case,
+
+    /// Case of names. None lowercases them unless the dialect's names are
+    /// case-sensitive.
+    #[serde(default)]
+    pub identifier_case: Option<Case>,
+
+    /// Case of function names, i.e. names directly followed by `(`.
+    /// None follows identifier_case.
+    #[serde(default)]
+    pub function_case: Option<Case>,
+
+    /// Whether split lists put commas at the start or end of lines.
+    #[serde(default)]
+    pub comma_style: CommaStyle,
+
+    /// Columns per indentation level. With tabs, the width a tab counts as
+    /// toward line_length.
+    #[serde(default = "default_indent_width")]
+    pub indent_width: usize,
+
+    /// Whether to indent with spaces or tabs.
+    #[serde(default)]
+    pub indent_style: IndentStyle,
+
+    /// Line up `as <alias>` and inline comments down split select lists.
+    #[serde(default)]
+    pub align_aliases: bool,
+
+    /// Insert `as` before bare column and table aliases.
+    #[serde(default)]
+    pub explicit_aliases: bool,
+
+    /// Operator spellings to standardize.
+    #[serde(default)]
+    pub normalize_operators: NormalizeOperators,
+
+    /// Write `"..."` string literals with single quotes, in dialects where
+    /// double quotes delimit strings.
+    #[serde(default)]
+    pub single_quote_strings: bool,
+
+    /// Drop parentheses that do not change evaluation order.
+    #[serde(default)]
+    pub remove_redundant_parens: bool,
+}
+
+/// Operator spellings to standardize, from `[normalize_operators]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NormalizeOperators {
+    /// Spelling of inequality. None keeps `<>` and `!=` as written.
+    pub not_equal: Option<NotEqual>,
+    /// Write DuckDB's `==` as `=`.
+    pub double_equals: bool,
+}
+
+/// Spelling of the inequality operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum NotEqual {
+    #[serde(rename = "<>")]
+    AngleBrackets,
+    #[serde(rename = "!=")]
+    BangEquals,
+}
+
+impl NotEqual {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotEqual::AngleBrackets => "<>",
+            NotEqual::BangEquals => "!=",
+        }
+    }
+}
+
+/// Character used for indentation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndentStyle {
+    #[default]
+    Spaces,
+    /// One tab per level.
+    Tabs,
+}
+
+/// Where commas go when a list is split one item per line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommaStyle {
+    /// `, b` at the start of each item's line.
+    #[default]
+    Leading,
+    /// `a,` at the end of each item's line.
+    Trailing,
+}
+
+/// Letter case applied to a class of tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    #[default]
+    Lower,
+    Upper,
+    Preserve,
+}
+
+/// A user-defined dialect: a built-in dialect with extra keywords.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DialectConfig {
+    /// The built-in dialect this one extends.
+    pub base: String,
+    pub unterm_keywords: Vec<String>,
+    pub word_operators: Vec<String>,
+    pub set_operators: Vec<String>,
+    /// First words of statements to pass through unformatted.
+    pub reserved_words: Vec<String>,
+    /// Overrides the base dialect's case sensitivity for names.
+    pub case_sensitive_names: Option<bool>,
+}
+
+impl Default for DialectConfig {
+    fn default() -> Self {
+        Self {
+            base: default_dialect(),
+            unterm_keywords: Vec::new(),
+            word_operators: Vec::new(),
+            set_operators: Vec::new(),
+            reserved_words: Vec::new(),
+            case_sensitive_names: None,
+        }
+    }
 }
 
 fn default_line_length() -> usize {
     88
 }
+fn default_indent_width() -> usize {
+    4
+}
 fn default_dialect() -> String {
     "polyglot".to_string()
 }
@@ -72,7 +224,26 @@ fn default_encoding() -> String {
 impl Mode {
     /// Create the dialect for the configured dialect_name.
     pub fn dialect(&self) -> Result<Box<dyn Dialect>, SqlfmtError> {
-        dialect::dialect_from_name(&self.dialect_name)
+        let config = match self.dialects.get(&self.dialect_name.to_ascii_lowercase()) {
+            Some(config) => config.clone(),
+            None if self.case_sensitive_names.is_some() => DialectConfig {
+                base: self.dialect_name.clone(),
+                ..DialectConfig::default()
+            },
+            None => return dialect::dialect_from_name(&self.dialect_name),
+        };
+        Ok(Box::new(CustomDialect::new(&DialectConfig {
+            case_sensitive_names: self.case_sensitive_names.or(config.case_sensitive_names),
+            ..config
+        })?))
+    }
+
+    /// Indentation settings for rendering lines.
+    pub fn indent(&self) -> Indent {
+        Indent {
+            width: self.indent_width,
+            style: self.indent_style,
+        }
     }
 
     /// Whether color output is enabled.
@@ -120,6 +291,19 @@ impl Default for Mode {
             threads: 0,
             single_process: false,
             reset_cache: false,
+            dialects: HashMap::new(),
+            case_sensitive_names: None,
+            keyword_case: Case::Lower,
+            identifier_case: None,
+            function_case: None,
+            comma_style: CommaStyle::Leading,
+            indent_width: 4,
+            indent_style: IndentStyle::Spaces,
+            align_aliases: false,
+            explicit_aliases: false,
+            normalize_operators: NormalizeOperators::default(),
+            single_quote_strings: false,
+            remove_redundant_parens: false,
         }
     }
 }
@@ -143,11 +327,28 @@ mod tests {
         let mode = Mode::default();
         assert!(mode.dialect().is_ok());
 
-        let mut duckdb_mode = Mode::default();
-        duckdb_mode.dialect_name = "duckdb".to_string();
+        let duckdb_mode = Mode {
+            dialect_name: "duckdb".to_string(),
+            ..Mode::default()
+        };
         assert!(duckdb_mode.dialect().is_ok());
     }
 
+    #[test]
+    fn test_case_sensitive_names() {
+        let mode = |dialect_name: &str, case_sensitive_names| Mode {
+            dialect_name: dialect_name.to_string(),
+            case_sensitive_names,
+            ..Mode::default()
+        };
+        let case_sensitive = |mode: Mode| mode.dialect().unwrap().case_sensitive_names();
+        assert!(case_sensitive(mode("clickhouse", None)));
+        assert!(!case_sensitive(mode("clickhouse", Some(false))));
+        assert!(!case_sensitive(mode("postgres", None)));
+        assert!(case_sensitive(mode("postgres", Some(true))));
+        assert!(mode("oracle", Some(true)).dialect().is_err());
+    }
+
     #[test]
     fn test_color_logic() {
         let mut mode = Mode::default();
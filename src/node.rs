@@ -24,6 +24,9 @@ pub struct Node {
     pub jinja_depth: u16,
     /// Whether formatting is disabled (fmt:off region).
     pub formatting_disabled: bool,
+    /// Whether this starts the single-statement body of a T-SQL `IF`,
+    /// `ELSE` or `WHILE`, which goes on its own line under the condition.
+    pub starts_condition_body: bool,
 }
 
 impl Node {
@@ -43,6 +46,7 @@ impl Node {
             bracket_depth,
             jinja_depth,
             formatting_disabled: false,
+            starts_condition_body: false,
         }
     }
 
@@ -109,6 +113,38 @@ impl Node {
         self.token.token_type.is_jinja()
     }
 
+    pub fn is_opening_block(&self) -> bool {
+        self.token.token_type.is_opening_block()
+    }
+
+    pub fn is_closing_block(&self) -> bool {
+        self.token.token_type == TokenType::BlockEnd
+    }
+
+    /// True for block keywords followed by a condition on the same line
+    /// (`if x then`, `while x do`, `elseif x then`, `when error then`),
+    /// as opposed to ones followed by the statements of the block body.
+    pub fn is_block_condition_keyword(&self) -> bool {
+        self.is_opening_block()
+            && ["if", "elseif", "elsif", "while", "for", "when"]
+                .iter()
+                .any(|kw| self.value.eq_ignore_ascii_case(kw))
+    }
+
+    /// True if the innermost bracket around this node is the `<` of a type
+    /// such as `struct<...>` or `array<...>`.
+    pub fn is_in_angle_brackets(&self, arena: &[Node]) -> bool {
+        let mut idx = self.previous_node;
+        while let Some(i) = idx {
+            let node = &arena[i];
+            if node.bracket_depth < self.bracket_depth {
+                return node.is_opening_bracket() && node.value == "<";
+            }
+            idx = node.previous_node;
+        }
+        false
+    }
+
     pub fn is_jinja_statement(&self) -> bool {
         self.token.token_type.is_jinja_statement()
     }
@@ -302,24 +338,23 @@ mod tests {
     #[test]
     fn test_is_multiplication_star() {
         // Star after a name => multiplication
-        let mut arena = Vec::new();
-        arena.push(make_node(TokenType::Name, "a", None));
+        let arena = vec![make_node(TokenType::Name, "a", None)];
         let mut star = make_node(TokenType::Star, "*", Some(0));
         star.token = Token::new(TokenType::Star, "", "*", 2, 3);
         assert!(star.is_multiplication_star(&arena));
 
         // Star after SELECT => not multiplication
-        let mut arena2 = Vec::new();
-        arena2.push(make_node(TokenType::UntermKeyword, "select", None));
+        let arena2 = vec![make_node(TokenType::UntermKeyword, "select", None)];
         let star2 = make_node(TokenType::Star, "*", Some(0));
         assert!(!star2.is_multiplication_star(&arena2));
     }
 
     #[test]
     fn test_get_previous_sql_token_skips_newline() {
-        let mut arena = Vec::new();
-        arena.push(make_node(TokenType::Name, "a", None));
-        arena.push(make_node(TokenType::Newline, "\n", Some(0)));
+        let arena = vec![
+            make_node(TokenType::Name, "a", None),
+            make_node(TokenType::Newline, "\n", Some(0)),
+        ];
         let node = make_node(TokenType::Name, "b", Some(1));
         let prev = node.get_previous_sql_token(&arena);
         assert!(prev.is_some());
@@ -345,22 +380,19 @@ mod tests {
     #[test]
     fn test_is_square_bracket_operator() {
         // Square bracket after a Name => bracket operator (array indexing)
-        let mut arena = Vec::new();
-        arena.push(make_node(TokenType::Name, "arr", None));
+        let arena = vec![make_node(TokenType::Name, "arr", None)];
         let mut bracket = make_node(TokenType::BracketOpen, "[", Some(0));
         bracket.value = CompactString::from("[");
         assert!(bracket.is_bracket_operator(&arena));
 
         // Square bracket after QuotedName => bracket operator
-        let mut arena2 = Vec::new();
-        arena2.push(make_node(TokenType::QuotedName, "\"my_col\"", None));
+        let arena2 = vec![make_node(TokenType::QuotedName, "\"my_col\"", None)];
         let mut bracket2 = make_node(TokenType::BracketOpen, "[", Some(0));
         bracket2.value = CompactString::from("[");
         assert!(bracket2.is_bracket_operator(&arena2));
 
         // Square bracket after BracketClose => bracket operator
-        let mut arena3 = Vec::new();
-        arena3.push(make_node(TokenType::BracketClose, "]", None));
+        let arena3 = vec![make_node(TokenType::BracketClose, "]", None)];
         let mut bracket3 = make_node(TokenType::BracketOpen, "[", Some(0));
         bracket3.value = CompactString::from("[");
         assert!(bracket3.is_bracket_operator(&arena3));
@@ -375,21 +407,23 @@ mod tests {
     #[test]
     fn test_is_the_and_after_the_between_operator() {
         // Build: BETWEEN x AND y
-        let mut arena = Vec::new();
-        // index 0: between
-        arena.push(make_node(TokenType::WordOperator, "between", None));
-        // index 1: x (name)
-        arena.push(make_node(TokenType::Name, "x", Some(0)));
-        // index 2: and (boolean operator)
-        arena.push(make_node(TokenType::BooleanOperator, "and", Some(1)));
+        let arena = vec![
+            // index 0: between
+            make_node(TokenType::WordOperator, "between", None),
+            // index 1: x (name)
+            make_node(TokenType::Name, "x", Some(0)),
+            // index 2: and (boolean operator)
+            make_node(TokenType::BooleanOperator, "and", Some(1)),
+        ];
 
         // The AND at index 2 should be recognized as "the AND after BETWEEN"
         assert!(arena[2].is_the_and_after_between(&arena));
 
         // A standalone AND without BETWEEN should NOT be
-        let mut arena2 = Vec::new();
-        arena2.push(make_node(TokenType::Name, "a", None));
-        arena2.push(make_node(TokenType::BooleanOperator, "and", Some(0)));
+        let arena2 = vec![
+            make_node(TokenType::Name, "a", None),
+            make_node(TokenType::BooleanOperator, "and", Some(0)),
+        ];
         assert!(!arena2[1].is_the_and_after_between(&arena2));
     }
 
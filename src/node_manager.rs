@@ -3,18 +3,50 @@ use std::borrow::Cow;
 use compact_str::CompactString;
 use smallvec::SmallVec;
 
+use crate::mode::{Case, NormalizeOperators};
 use crate::node::{BracketVec, JinjaBlockVec, Node, NodeIndex};
+use crate::string_utils::to_single_quoted;
 use crate::token::{Token, TokenType};
 
+/// How an unterm keyword opens its clause relative to the open clauses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClauseNesting {
+    /// Replaces the current clause.
+    #[default]
+    Replace,
+    /// Nests inside the current clause, like the statement after a T-SQL
+    /// `IF` condition.
+    Nest,
+    /// Closes every open clause, like the statement after a T-SQL `IF`
+    /// condition's body.
+    CloseAll,
+}
+
 /// NodeManager creates Nodes from Tokens, tracking bracket depth,
 /// whitespace rules, and formatting state.
 #[derive(Debug, Clone)]
 pub struct NodeManager {
-    pub case_sensitive_names: bool,
+    pub keyword_case: Case,
+    /// Case of names. Preserve for dialects with case-sensitive names.
+    pub identifier_case: Case,
+    /// Case of names directly followed by `(`, as in `count(*)`.
+    pub function_case: Case,
+    /// Operator spellings to rewrite, such as `!=` to `<>`.
+    pub operators: NormalizeOperators,
+    /// Rewrite `"..."` string literals with single quotes. Only set for
+    /// dialects where double quotes delimit strings rather than names.
+    pub single_quote_strings: bool,
     /// Filtered brackets (no unterm keywords) — used by actions like HandleNonreservedTopLevelKeyword.
     pub open_brackets: BracketVec,
     /// Current jinja block stack — used by handle_jinja_block_keyword etc.
     pub open_jinja_blocks: JinjaBlockVec,
+    /// Open sections of procedural blocks (BEGIN, THEN, ELSE, LOOP, ...).
+    /// Kept out of open_brackets so statements inside a block are still
+    /// lexed as top-level statements.
+    pub open_blocks: BracketVec,
+    /// How the next unterm keyword opens its clause. Set by the analyzer,
+    /// and reset once that keyword is added.
+    pub next_clause: ClauseNesting,
     /// Formatting-disabled nesting depth. >0 means formatting is disabled.
     /// Uses a counter instead of bool to handle nested Data token push/pop.
     formatting_disabled_depth: u16,
@@ -32,10 +64,21 @@ pub struct NodeManager {
 
 impl NodeManager {
     pub fn new(case_sensitive_names: bool) -> Self {
+        let name_case = if case_sensitive_names {
+            Case::Preserve
+        } else {
+            Case::Lower
+        };
         Self {
-            case_sensitive_names,
+            keyword_case: Case::Lower,
+            identifier_case: name_case,
+            function_case: name_case,
+            operators: NormalizeOperators::default(),
+            single_quote_strings: false,
             open_brackets: SmallVec::new(),
             open_jinja_blocks: SmallVec::new(),
+            open_blocks: SmallVec::new(),
+            next_clause: ClauseNesting::Replace,
             formatting_disabled_depth: 0,
             node_open_brackets: SmallVec::new(),
             node_open_jinja: SmallVec::new(),
@@ -76,6 +119,7 @@ impl NodeManager {
             bracket_depth,
             jinja_depth,
             formatting_disabled,
+            starts_condition_body: false,
         }
     }
 
@@ -107,7 +151,8 @@ impl NodeManager {
                 // LATERAL is an unterm keyword for splitting but does NOT
                 // increase depth for the next node — it's a FROM clause
                 // modifier, not a clause-level keyword.
-                if prev.is_unterm_keyword() || prev.is_opening_bracket() {
+                if prev.is_unterm_keyword() || prev.is_opening_bracket() || prev.is_opening_block()
+                {
                     let is_lateral_kw =
                         prev.is_unterm_keyword() && prev.value.eq_ignore_ascii_case("lateral");
                     if !is_lateral_kw {
@@ -128,12 +173,24 @@ impl NodeManager {
                 // LATERAL should NOT pop the previous keyword — it's a modifier
                 // within the FROM clause, not a replacement for FROM.
                 let is_lateral = token.text.eq_ignore_ascii_case("lateral");
-                if !is_lateral {
-                    if let Some(last) = self.node_open_brackets.last() {
-                        if arena[*last].is_unterm_keyword() {
-                            self.node_open_brackets.pop();
+                match std::mem::take(&mut self.next_clause) {
+                    ClauseNesting::Replace if !is_lateral => {
+                        if let Some(last) = self.node_open_brackets.last() {
+                            if arena[*last].is_unterm_keyword() {
+                                self.node_open_brackets.pop();
+                            }
                         }
                     }
+                    ClauseNesting::CloseAll => {
+                        while let Some(last) = self.node_open_brackets.last() {
+                            if arena[*last].is_unterm_keyword() {
+                                self.node_open_brackets.pop();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    _ => {}
                 }
             }
             TokenType::BracketClose | TokenType::StatementEnd => {
@@ -149,34 +206,79 @@ impl NodeManager {
             TokenType::JinjaBlockEnd => {
                 // Pop the jinja block and restore SQL brackets to the state
                 // at the time the jinja block was opened.
-                if self.node_open_jinja.pop().is_some() {
-                    if let Some(snapshot) = self.node_open_jinja_bracket_snapshots.pop() {
-                        self.node_open_brackets = snapshot;
-                    }
+                if let Some(snapshot) = self
+                    .node_open_jinja
+                    .pop()
+                    .and_then(|_| self.node_open_jinja_bracket_snapshots.pop())
+                {
+                    self.node_open_brackets = snapshot;
                 }
             }
             TokenType::JinjaBlockKeyword => {
                 // {% else %}, {% elif %}, etc. close the previous block section
                 // and open a new one. Restore SQL brackets to the block start's state.
-                if self.node_open_jinja.pop().is_some() {
-                    if let Some(snapshot) = self.node_open_jinja_bracket_snapshots.pop() {
-                        self.node_open_brackets = snapshot;
+                if let Some(snapshot) = self
+                    .node_open_jinja
+                    .pop()
+                    .and_then(|_| self.node_open_jinja_bracket_snapshots.pop())
+                {
+                    self.node_open_brackets = snapshot;
+                }
+            }
+            TokenType::BlockStart => {
+                // A block that follows a clause (T-SQL `IF x BEGIN`) starts
+                // at the clause's depth
+                while let Some(last) = self.node_open_brackets.last() {
+                    if arena[*last].is_unterm_keyword() {
+                        self.node_open_brackets.pop();
+                    } else {
+                        break;
+                    }
+                }
+            }
+            TokenType::BlockKeyword | TokenType::BlockEnd => {
+                // THEN, ELSE, EXCEPTION, END IF, etc. close the current section
+                // of the block (and its header keywords); BlockKeyword then
+                // opens a new section at the same depth.
+                while let Some(last) = self.node_open_brackets.last() {
+                    if arena[*last].is_unterm_keyword() {
+                        self.node_open_brackets.pop();
+                    } else {
+                        break;
+                    }
+                }
+                if let Some(last) = self.node_open_brackets.last() {
+                    if arena[*last].is_opening_block() {
+                        self.node_open_brackets.pop();
                     }
                 }
             }
             TokenType::Semicolon => {
-                self.node_open_brackets.clear();
+                // A semicolon ends the statement, but not the procedural
+                // block that contains it.
+                let block_len = self
+                    .node_open_brackets
+                    .iter()
+                    .rposition(|&idx| arena[idx].is_opening_block())
+                    .map_or(0, |pos| pos + 1);
+                self.node_open_brackets.truncate(block_len);
             }
             _ => {}
         }
 
-        // NodeManager's open_brackets: ONLY actual brackets, not unterm keywords.
-        // This is used by HandleNonreservedTopLevelKeyword to decide if FROM/USING
-        // should be treated as keywords or names.
+        // NodeManager's open_brackets: ONLY actual brackets, not unterm keywords
+        // or block sections. This is used by HandleNonreservedTopLevelKeyword to
+        // decide if FROM/USING should be treated as keywords or names.
         self.open_brackets = self
             .node_open_brackets
             .iter()
-            .filter(|&&idx| !arena[idx].is_unterm_keyword())
+            .filter(|&&idx| !arena[idx].is_unterm_keyword() && !arena[idx].is_opening_block())
+            .copied()
+            .collect();
+        self.open_blocks = self
+            .node_open_brackets
+            .iter()
+            .filter(|&&idx| arena[idx].is_opening_block())
             .copied()
             .collect();
         self.open_jinja_blocks = self.node_open_jinja.clone();
@@ -290,6 +392,8 @@ impl NodeManager {
                                 | TokenType::Comma
                                 | TokenType::BracketOpen
                                 | TokenType::StatementStart
+                                | TokenType::BlockStart
+                                | TokenType::BlockKeyword
                                 | TokenType::SetOperator
                                 | TokenType::Star
                                 | TokenType::On
@@ -379,6 +483,21 @@ impl NodeManager {
                     | Some(TokenType::BracketClose)
             )
         {
+            // INSERT INTO tbl (col, ...), CREATE TABLE tbl (col type, ...) and
+            // REFERENCES tbl (col): the column list is not a function call
+            if let Some(prev_node) = prev {
+                if Self::is_table_before_column_list(prev_node, arena) {
+                    return Cow::Borrowed(" ");
+                }
+                // A stage reference keeps the space before its options as
+                // written: `@stage/path/ (file_format => ...)`
+                if prev_node.is_quoted_name()
+                    && prev_node.value.starts_with('@')
+                    && !token.prefix.is_empty()
+                {
+                    return Cow::Borrowed(" ");
+                }
+            }
             if prev_type == Some(TokenType::Name) {
                 if let Some(prev_node) = prev {
                     // Snowflake DDL: before(, at( always need a space
@@ -459,29 +578,71 @@ impl NodeManager {
         }
     }
 
-    /// Standardize the token value: lowercase keywords, normalize whitespace, preserve names.
+    /// True if `node` ends the (possibly qualified) table name that directly
+    /// follows an INSERT, COPY INTO, CREATE TABLE or REFERENCES keyword, e.g.
+    /// `t` in `insert into s.t (a, b)`.
+    fn is_table_before_column_list(node: &Node, arena: &[Node]) -> bool {
+        let mut current = node;
+        loop {
+            if !matches!(
+                current.token.token_type,
+                TokenType::Name | TokenType::QuotedName | TokenType::Dot
+            ) {
+                let kw = current.value.to_ascii_lowercase();
+                return match current.token.token_type {
+                    TokenType::UntermKeyword => {
+                        kw.starts_with("insert")
+                            || kw == "copy into"
+                            || (kw.starts_with("create")
+                                && !kw.contains("function")
+                                && kw.split_whitespace().any(|w| w == "table"))
+                    }
+                    TokenType::DdlKeyword => kw == "references",
+                    _ => false,
+                };
+            }
+            match Self::get_previous_token(current.previous_node, arena) {
+                (Some(prev), _) => current = prev,
+                (None, _) => return false,
+            }
+        }
+    }
+
+    /// Standardize the token value: case keywords per keyword_case, normalize
+    /// whitespace, preserve names.
     /// Mirrors Python's standardize_value which also normalizes internal whitespace
     /// in multi-word keywords (e.g., "ORDER  BY" => "order by").
     /// Returns Cow::Borrowed when the value doesn't need changing (fast path).
     fn standardize_value<'a>(&self, token: &'a Token) -> Cow<'a, str> {
         let tt = token.token_type;
 
-        if tt.is_always_lowercased() {
-            // Fast path: if already lowercase and single-word, borrow directly
-            if !token.text.contains(|c: char| c.is_ascii_whitespace())
-                && token.text.bytes().all(|b| !b.is_ascii_uppercase())
-            {
+        // T-SQL GO and MySQL DELIMITER lines end a statement like a
+        // semicolon, and are cased like keywords
+        let is_word_terminator =
+            tt == TokenType::Semicolon && token.text.starts_with(|c: char| c.is_ascii_alphabetic());
+        if tt.is_keyword() || is_word_terminator {
+            let needs_case = match self.keyword_case {
+                Case::Lower => token.text.bytes().any(|b| b.is_ascii_uppercase()),
+                Case::Upper => token.text.bytes().any(|b| b.is_ascii_lowercase()),
+                Case::Preserve => false,
+            };
+            // Fast path: if already cased and single-word, borrow directly
+            if !needs_case && !token.text.contains(|c: char| c.is_ascii_whitespace()) {
                 return Cow::Borrowed(&*token.text);
             }
-            // Lowercase in-place on a String buffer (avoids str::to_ascii_lowercase
+            // Case in-place on a String buffer (avoids str::to_ascii_lowercase
             // which allocates a separate String)
-            let mut lower = String::from(&*token.text);
-            lower.make_ascii_lowercase();
-            if !lower.contains(|c: char| c.is_ascii_whitespace()) {
-                return Cow::Owned(lower);
+            let mut cased = String::from(&*token.text);
+            match self.keyword_case {
+                Case::Lower => cased.make_ascii_lowercase(),
+                Case::Upper => cased.make_ascii_uppercase(),
+                Case::Preserve => {}
+            }
+            if !cased.contains(|c: char| c.is_ascii_whitespace()) {
+                return Cow::Owned(cased);
             }
-            let mut result = String::with_capacity(lower.len());
-            for (i, word) in lower.split_whitespace().enumerate() {
+            let mut result = String::with_capacity(cased.len());
+            for (i, word) in cased.split_whitespace().enumerate() {
                 if i > 0 {
                     result.push(' ');
                 }
@@ -499,23 +660,94 @@ impl NodeManager {
             return Cow::Owned(s);
         }
 
-        if !self.case_sensitive_names && tt == TokenType::Name {
-            let first = token.text.as_bytes().first().copied();
-            if matches!(first, Some(b'\'') | Some(b'$')) {
-                return Cow::Borrowed(&*token.text);
-            }
-            if token.text.bytes().all(|b| !b.is_ascii_uppercase()) {
-                return Cow::Borrowed(&*token.text);
+        if tt == TokenType::Name {
+            if self.single_quote_strings {
+                if let Some(s) = to_single_quoted(&token.text) {
+                    return Cow::Owned(s);
+                }
             }
-            let mut s = String::from(&*token.text);
-            s.make_ascii_lowercase();
-            return Cow::Owned(s);
+            return Self::case_name(&token.text, self.identifier_case);
+        }
+
+        if tt == TokenType::Operator {
+            return Cow::Borrowed(self.normalize_operator(&token.text));
+        }
+
+        // Types are cased like names: ClickHouse types are case-sensitive
+        if tt == TokenType::DataType {
+            let spaced = Self::space_type_params(&token.text);
+            return match Self::case_name(&spaced, self.identifier_case) {
+                Cow::Borrowed(_) => spaced,
+                Cow::Owned(s) => Cow::Owned(s),
+            };
         }
 
         // Jinja tokens, quoted names, etc.: preserve original text
         Cow::Borrowed(&*token.text)
     }
 
+    /// The configured spelling of an operator: `<>` or `!=` for inequality,
+    /// and `=` for `==`.
+    fn normalize_operator<'a>(&self, text: &'a str) -> &'a str {
+        match text {
+            "<>" | "!=" => self.operators.not_equal.map_or(text, |ne| ne.as_str()),
+            "==" if self.operators.double_equals => "=",
+            _ => text,
+        }
+    }
+
+    /// Re-case a name node that turned out to be a function call, i.e. the
+    /// next node is a `(` with no space before it.
+    pub fn case_function_name(&self, node: &mut Node) {
+        if self.function_case != self.identifier_case {
+            node.value = CompactString::from(Self::case_name(&node.token.text, self.function_case));
+        }
+    }
+
+    fn case_name(text: &str, case: Case) -> Cow<'_, str> {
+        let first = text.as_bytes().first().copied();
+        // String literals, dollar-quoted bodies and placeholders such as `%s`
+        if case == Case::Preserve || matches!(first, Some(b'\'' | b'$' | b'%')) {
+            return Cow::Borrowed(text);
+        }
+        // Prefixed literals (E'...', U&'...', r'''...''') keep their contents
+        let end = text.find(['\'', '"']).unwrap_or(text.len());
+        let needs_case = match case {
+            Case::Lower => text[..end].bytes().any(|b| b.is_ascii_uppercase()),
+            Case::Upper => text[..end].bytes().any(|b| b.is_ascii_lowercase()),
+            Case::Preserve => false,
+        };
+        if !needs_case {
+            return Cow::Borrowed(text);
+        }
+        let mut s = String::from(text);
+        if case == Case::Upper {
+            s[..end].make_ascii_uppercase();
+        } else {
+            s[..end].make_ascii_lowercase();
+        }
+        Cow::Owned(s)
+    }
+
+    /// Write a column type's parameters as `decimal(12, 2)`, whatever the
+    /// spacing in the source.
+    fn space_type_params(text: &str) -> Cow<'_, str> {
+        let Some(open) = text.find('(') else {
+            return Cow::Borrowed(text);
+        };
+        let params = text[open + 1..text.len() - 1]
+            .split(',')
+            .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let spaced = format!("{}({})", text[..open].trim_end(), params);
+        if spaced == text {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(spaced)
+        }
+    }
+
     /// Enable formatting (handle fmt:on).
     #[cfg(test)]
     pub fn enable_formatting(&mut self) {
@@ -557,6 +789,51 @@ mod tests {
         assert_eq!(nm.standardize_value(&token), "select");
     }
 
+    #[test]
+    fn test_standardize_value_keyword_case() {
+        let mut nm = NodeManager::new(false);
+        let token = Token::new(TokenType::UntermKeyword, "", "Group\n  By", 0, 11);
+        nm.keyword_case = Case::Upper;
+        assert_eq!(nm.standardize_value(&token), "GROUP BY");
+        nm.keyword_case = Case::Preserve;
+        assert_eq!(nm.standardize_value(&token), "Group By");
+        let name = Token::new(TokenType::Name, "", "MyTable", 0, 7);
+        assert_eq!(nm.standardize_value(&name), "mytable");
+    }
+
+    #[test]
+    fn test_standardize_value_normalizes_operators() {
+        let mut nm = NodeManager::new(false);
+        let op = |text: &str| Token::new(TokenType::Operator, "", text, 0, text.len() as u32);
+        assert_eq!(nm.standardize_value(&op("!=")), "!=");
+        assert_eq!(nm.standardize_value(&op("==")), "==");
+        nm.operators = NormalizeOperators {
+            not_equal: Some(crate::mode::NotEqual::AngleBrackets),
+            double_equals: true,
+        };
+        assert_eq!(nm.standardize_value(&op("!=")), "<>");
+        assert_eq!(nm.standardize_value(&op("<>")), "<>");
+        assert_eq!(nm.standardize_value(&op("==")), "=");
+        assert_eq!(nm.standardize_value(&op("||")), "||");
+    }
+
+    #[test]
+    fn test_identifier_and_function_case() {
+        let mut nm = NodeManager::new(true);
+        nm.function_case = Case::Upper;
+        let token = Token::new(TokenType::Name, "", "toDate", 0, 6);
+        let mut node = nm.create_node(token, None, &[]);
+        assert_eq!(node.value, "toDate");
+        nm.case_function_name(&mut node);
+        assert_eq!(node.value, "TODATE");
+
+        nm.identifier_case = Case::Upper;
+        let token = Token::new(TokenType::Name, "", "u&'\\0041'", 0, 9);
+        assert_eq!(nm.standardize_value(&token), "U&'\\0041'");
+        let token = Token::new(TokenType::Name, "", "%(id)s", 0, 6);
+        assert_eq!(nm.standardize_value(&token), "%(id)s");
+    }
+
     #[test]
     fn test_standardize_value_lowercases_names_when_insensitive() {
         let nm = NodeManager::new(false);
@@ -564,6 +841,13 @@ mod tests {
         assert_eq!(nm.standardize_value(&token), "mytable");
     }
 
+    #[test]
+    fn test_standardize_value_preserves_prefixed_string_contents() {
+        let nm = NodeManager::new(false);
+        let token = Token::new(TokenType::Name, "", "E'Line\\nTwo'", 0, 12);
+        assert_eq!(nm.standardize_value(&token), "e'Line\\nTwo'");
+    }
+
     #[test]
     fn test_standardize_value_preserves_names_when_sensitive() {
         let nm = NodeManager::new(true);
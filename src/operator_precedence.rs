@@ -80,6 +80,15 @@ impl OperatorPrecedence {
         }
     }
 
+    /// Determine precedence from a Node in a dialect where `:=` assigns a
+    /// variable (MySQL), so it binds like `=`.
+    pub fn from_node_with_assignment(node: &Node, arena: &[Node]) -> Self {
+        if node.token.token_type == TokenType::Operator && node.value == ":=" {
+            return Self::Comparators;
+        }
+        Self::from_node(node, arena)
+    }
+
     /// Classify a word operator without allocating. Uses case-insensitive
     /// comparison with whitespace normalization for multi-word operators.
     fn from_word_operator(value: &str) -> Self {
@@ -142,15 +151,15 @@ impl OperatorPrecedence {
         Self::Other
     }
 
+    /// Classify a symbol operator. Postgres operators other than arithmetic
+    /// and comparison (`@>`, `?|`, `#>>`, `||/`, `~*`, `!~~`, ...) share one
+    /// level, binding tighter than comparisons and looser than `+` / `-`.
     fn from_symbol_operator(value: &str) -> Self {
         match value {
             "**" => Self::Exponent,
             "*" | "/" | "%" | "||" => Self::Multiplication,
             "+" | "-" => Self::Addition,
-            "=" | "==" | "!=" | "<>" | "<" | ">" | "<=" | ">=" | "<=>" | "~" | "!~" | "~*"
-            | "!~*" | "@>" | "<@" | "@@" | "<->" | "!!" | "&&" | "?|" | "?&" | "-|-" => {
-                Self::Comparators
-            }
+            "=" | "==" | "!=" | "<>" | "<" | ">" | "<=" | ">=" | "<=>" => Self::Comparators,
             _ => Self::Other,
         }
     }
@@ -245,6 +254,15 @@ mod tests {
             OperatorPrecedence::from_node(&eq, &arena),
             OperatorPrecedence::Comparators
         );
+        let assign = make_node(TokenType::Operator, ":=");
+        assert_eq!(
+            OperatorPrecedence::from_node(&assign, &arena),
+            OperatorPrecedence::Other
+        );
+        assert_eq!(
+            OperatorPrecedence::from_node_with_assignment(&assign, &arena),
+            OperatorPrecedence::Comparators
+        );
         assert_eq!(
             OperatorPrecedence::from_node(&exp, &arena),
             OperatorPrecedence::Exponent
@@ -326,15 +344,18 @@ mod tests {
     }
 
     #[test]
-    fn test_pg_comparison_operators() {
+    fn test_pg_operators() {
         let arena = vec![];
 
-        for op in &["@>", "<@", "@@", "<->", "&&", "?|", "?&", "-|-"] {
+        for op in &[
+            "@>", "<@", "@@", "<->", "&&", "?|", "?&", "-|-", "#>>", "||/", "~", "~*", "!~", "!~~",
+            "~~*", "^@", ">>=",
+        ] {
             let node = make_node(TokenType::Operator, op);
             assert_eq!(
                 OperatorPrecedence::from_node(&node, &arena),
-                OperatorPrecedence::Comparators,
-                "Expected Comparators for '{}'",
+                OperatorPrecedence::Other,
+                "Expected Other for '{}'",
                 op
             );
         }
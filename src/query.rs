@@ -1,4 +1,4 @@
-use crate::line::Line;
+use crate::line::{Indent, Line};
 use crate::node::Node;
 
 /// A Query holds the source string, formatting configuration,
@@ -7,6 +7,7 @@ use crate::node::Node;
 pub struct Query {
     pub source_string: String,
     pub line_length: usize,
+    pub indent: Indent,
     pub lines: Vec<Line>,
 }
 
@@ -15,6 +16,7 @@ impl Query {
         Self {
             source_string,
             line_length,
+            indent: Indent::default(),
             lines,
         }
     }
@@ -33,6 +35,7 @@ impl Query {
             };
             result.push_str(&line.render_with_comments(
                 arena,
+                self.indent,
                 self.line_length,
                 indent_override.as_deref(),
             ));
@@ -55,7 +58,7 @@ impl Query {
                     return Some(prefix.to_string());
                 }
             }
-            return Some(next.indentation(arena).to_string());
+            return Some(next.indentation(arena, self.indent).to_string());
         }
         None
     }
@@ -0,0 +1,288 @@
+use crate::node::Node;
+use crate::operator_precedence::OperatorPrecedence;
+use crate::query::Query;
+use crate::token::TokenType;
+
+/// Unterm keywords that introduce a single expression, which may be
+/// wrapped in redundant parentheses.
+const EXPRESSION_CLAUSES: &[&str] = &[
+    "where",
+    "having",
+    "qualify",
+    "when",
+    "then",
+    "else",
+    "group by",
+    "order by",
+    "partition by",
+];
+
+/// What a parenthesized expression borders on one side.
+#[derive(Clone, Copy)]
+enum ParenContext {
+    /// A clause keyword, comma or enclosing bracket.
+    Boundary,
+    /// An operator binding at this precedence.
+    Operator(OperatorPrecedence),
+    /// Syntax that needs the brackets: a function call, `in (...)`,
+    /// `values (...)`, `(a).b`, ...
+    Required,
+}
+
+/// Find the parentheses that do not change evaluation order, such as both
+/// pairs in `where ((a = 1))`, and return their bracket nodes. Pairs are
+/// checked outermost first, so an inner pair sees the operators around the
+/// outer one once the outer one is gone.
+pub(crate) fn redundant_parens(query: &Query, arena: &[Node]) -> Vec<usize> {
+    let content: Vec<usize> = query
+        .lines
+        .iter()
+        .flat_map(|l| l.nodes.iter().copied())
+        .filter(|&i| !arena[i].is_newline())
+        .collect();
+    // position of the `)` that closes each `(`: the first node after it
+    // that is back at its depth
+    let closes: Vec<Option<usize>> = content
+        .iter()
+        .enumerate()
+        .map(|(k, &idx)| {
+            let o = &arena[idx];
+            if o.token.token_type != TokenType::BracketOpen {
+                return None;
+            }
+            content[k + 1..]
+                .iter()
+                .position(|&i| arena[i].bracket_depth <= o.bracket_depth)
+                .map(|j| k + 1 + j)
+                .filter(|&c| arena[content[c]].is_closing_bracket())
+        })
+        .collect();
+
+    let mut removed = vec![false; content.len()];
+    for k in 0..content.len() {
+        if let Some(c) = closes[k] {
+            if paren_is_redundant(arena, &content, &removed, k, c) {
+                removed[k] = true;
+                removed[c] = true;
+            }
+        }
+    }
+    content
+        .into_iter()
+        .zip(removed)
+        .filter_map(|(idx, r)| r.then_some(idx))
+        .collect()
+}
+
+/// True if the brackets at content positions `open` and `close` can be
+/// dropped: the expression between them binds tighter than the operators
+/// on either side, and neither side needs the brackets.
+fn paren_is_redundant(
+    arena: &[Node],
+    content: &[usize],
+    removed: &[bool],
+    open: usize,
+    close: usize,
+) -> bool {
+    use ParenContext::*;
+
+    let (o, c) = (&arena[content[open]], &arena[content[close]]);
+    if o.value != "("
+        || close == open + 1
+        || o.depth() != c.depth()
+        || o.formatting_disabled
+        || c.formatting_disabled
+    {
+        return false;
+    }
+
+    // the loosest operator directly inside; None for a single operand
+    let depth = o.bracket_depth + 1;
+    let mut inner = None;
+    for (j, &idx) in content[open + 1..close].iter().enumerate() {
+        let n = &arena[idx];
+        // a jinja expression may render as any expression
+        if n.formatting_disabled || n.is_jinja() || n.bracket_depth < depth {
+            return false;
+        }
+        if n.bracket_depth > depth {
+            continue;
+        }
+        match n.token.token_type {
+            // a leading unary operator could join the one before: `-(-a)`
+            TokenType::Operator if j == 0 => return false,
+            TokenType::Operator
+            | TokenType::BooleanOperator
+            | TokenType::DoubleColon
+            | TokenType::Star => {}
+            TokenType::WordOperator if !n.value.eq_ignore_ascii_case("as") => {}
+            TokenType::Name
+            | TokenType::QuotedName
+            | TokenType::Number
+            | TokenType::Dot
+            | TokenType::BracketOpen
+            | TokenType::BracketClose
+            | TokenType::StatementStart
+            | TokenType::StatementEnd => continue,
+            // commas (tuples), clause keywords (subqueries), ...
+            _ => return false,
+        }
+        match evaluation_precedence(n, arena) {
+            Some(p) => inner = inner.max(Some(p)),
+            None => return false,
+        }
+    }
+
+    let is_expression_clause = |n: &Node| {
+        let kw = n.value.to_ascii_lowercase();
+        kw.starts_with("select") || EXPRESSION_CLAUSES.contains(&kw.as_str())
+    };
+    // the clause or bracket the expression belongs to: not a values row,
+    // or a DDL property list such as `imports = ('...')`
+    let enclosing = content[..open]
+        .iter()
+        .map(|&i| &arena[i])
+        .rfind(|m| m.bracket_depth < o.bracket_depth);
+    let in_expression = enclosing.is_some_and(|m| {
+        m.token.token_type == TokenType::BracketOpen
+            || m.is_unterm_keyword()
+                && (is_expression_clause(m) || m.value.to_ascii_lowercase().ends_with("join"))
+    });
+    if !in_expression {
+        return false;
+    }
+
+    let before = (0..open).rev().find(|&k| !removed[k]);
+    let left = match before.map(|k| &arena[content[k]]) {
+        None => Boundary,
+        Some(n) => match n.token.token_type {
+            // `select distinct on (a)`
+            TokenType::On
+                if before
+                    .and_then(|k| (0..k).rev().find(|&j| !removed[j]))
+                    .is_some_and(|j| {
+                        arena[content[j]]
+                            .value
+                            .to_ascii_lowercase()
+                            .starts_with("select")
+                    }) =>
+            {
+                Required
+            }
+            TokenType::BracketOpen | TokenType::On | TokenType::Comma => Boundary,
+            TokenType::UntermKeyword if is_expression_clause(n) => Boundary,
+            // `like`, `is`, ... but not `in (...)`, `over (...)`, `* except (...)`
+            TokenType::WordOperator if takes_brackets(&n.value) => Required,
+            TokenType::WordOperator
+            | TokenType::Operator
+            | TokenType::BooleanOperator
+            | TokenType::DoubleColon
+            | TokenType::Star => evaluation_precedence(n, arena).map_or(Required, Operator),
+            _ => Required,
+        },
+    };
+    let after = (close + 1..content.len()).find(|&k| !removed[k]);
+    let right = match after.map(|k| &arena[content[k]]) {
+        None => Boundary,
+        Some(n) => match n.token.token_type {
+            TokenType::BracketClose
+            | TokenType::Comma
+            | TokenType::Semicolon
+            | TokenType::UntermKeyword
+            | TokenType::SetOperator
+            | TokenType::On
+            | TokenType::StatementEnd
+            | TokenType::Name
+            | TokenType::QuotedName => Boundary,
+            TokenType::WordOperator if n.value.eq_ignore_ascii_case("as") => Boundary,
+            TokenType::WordOperator
+            | TokenType::Operator
+            | TokenType::BooleanOperator
+            | TokenType::DoubleColon
+            | TokenType::Star => evaluation_precedence(n, arena).map_or(Required, Operator),
+            _ => Required,
+        },
+    };
+
+    match (left, right) {
+        (Required, _) | (_, Required) => false,
+        _ => inner.is_none_or(|p| {
+            [left, right].iter().all(|side| match side {
+                // `and` and `or` are associative
+                Operator(q) => {
+                    p < *q
+                        || p == *q
+                            && matches!(p, OperatorPrecedence::BoolAnd | OperatorPrecedence::BoolOr)
+                }
+                _ => true,
+            })
+        }),
+    }
+}
+
+/// How tightly an operator binds when the query runs, which is not how its
+/// lines break: `||` is laid out like `*`, but concatenates after `+` in
+/// Postgres and is `or` in MySQL. None for operators whose evaluation order
+/// differs between dialects (`||`, `^`, `#`, `&`, `:=`, `->`, ...) or that
+/// are not infix operators (`over`, `interval`, ...), so brackets next to
+/// them are kept.
+fn evaluation_precedence(n: &Node, arena: &[Node]) -> Option<OperatorPrecedence> {
+    match n.token.token_type {
+        TokenType::DoubleColon => Some(OperatorPrecedence::DoubleColon),
+        TokenType::Star if n.is_multiplication_star(arena) => {
+            Some(OperatorPrecedence::Multiplication)
+        }
+        TokenType::Operator => match n.value.as_str() {
+            "*" | "/" | "%" => Some(OperatorPrecedence::Multiplication),
+            "+" | "-" => Some(OperatorPrecedence::Addition),
+            "=" | "==" | "!=" | "<>" | "<" | ">" | "<=" | ">=" | "<=>" => {
+                Some(OperatorPrecedence::Comparators)
+            }
+            _ => None,
+        },
+        // `between a and b` binds like `between`
+        TokenType::BooleanOperator if n.is_the_and_after_between(arena) => {
+            Some(OperatorPrecedence::Comparators)
+        }
+        TokenType::BooleanOperator | TokenType::WordOperator => {
+            match OperatorPrecedence::from_node(n, arena) {
+                // `in`, `like` and `between` share a level with comparisons
+                // in some dialects (MySQL)
+                OperatorPrecedence::Membership => Some(OperatorPrecedence::Comparators),
+                p @ (OperatorPrecedence::Presence
+                | OperatorPrecedence::BoolNot
+                | OperatorPrecedence::BoolAnd
+                | OperatorPrecedence::BoolOr) => Some(p),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Membership and presence operators followed by a bracketed list or
+/// subquery: `in (...)`, `exists (...)`, `like any (...)`.
+fn takes_brackets(value: &str) -> bool {
+    let last = value.split_whitespace().last().unwrap_or_default();
+    ["in", "exists", "any", "all"]
+        .iter()
+        .any(|w| last.eq_ignore_ascii_case(w))
+}
+
+/// Replace the bracket of each node in `nodes` with a space.
+pub(crate) fn strip_nodes(source: &str, arena: &[Node], nodes: &[usize]) -> String {
+    let mut starts: Vec<usize> = nodes
+        .iter()
+        .map(|&i| arena[i].token.epos as usize - arena[i].token.text.len())
+        .collect();
+    starts.sort_unstable();
+    let mut result = String::with_capacity(source.len());
+    let mut last = 0;
+    for start in starts {
+        result.push_str(&source[last..start]);
+        result.push(' ');
+        last = start + 1;
+    }
+    result.push_str(&source[last..]);
+    result
+}
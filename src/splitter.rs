@@ -84,7 +84,7 @@ impl LineSplitter {
         // split from preceding content (e.g., `= {{ ... }}` or `on {{ ... }}`).
         // Note: the operator/keyword split rules below will still break lines
         // at operators and keywords that precede multiline Jinja.
-        if node.is_unterm_keyword() {
+        if node.is_unterm_keyword() || node.starts_condition_body {
             return true;
         }
         if node.is_opening_jinja_block() {
@@ -127,6 +127,9 @@ impl LineSplitter {
         if node.is_closing_jinja_block() {
             return true;
         }
+        if node.is_closing_block() || node.token.token_type == TokenType::BlockStart {
+            return true;
+        }
         if node.divides_queries() {
             return true;
         }
@@ -135,9 +138,10 @@ impl LineSplitter {
             return true;
         }
 
-        // Leading-comma style: split BEFORE commas so they lead the next line
+        // Leading-comma style: split BEFORE commas so they lead the next line,
+        // except between the parameters of a type like `struct<a int64, b string>`
         if node.is_comma() {
-            return true;
+            return !node.is_in_angle_brackets(arena);
         }
 
         false
@@ -184,6 +188,11 @@ impl LineSplitter {
             }
             return (true, false);
         }
+        // The statements of a procedural block start on a new line, but
+        // `if`, `while`, `elseif`, etc. keep their condition on theirs
+        if node.is_opening_block() {
+            return (!node.is_block_condition_keyword(), false);
+        }
         // not after LATERAL when followed by ( (it should stay as "lateral(" like a function call)
         if node.is_unterm_keyword() {
             if node.value.eq_ignore_ascii_case("lateral") {
@@ -192,6 +201,13 @@ impl LineSplitter {
                     return (false, false);
                 }
             }
+            // T-SQL `if` and `while` keep their condition on their line
+            if ["if", "while"]
+                .iter()
+                .any(|kw| node.value.eq_ignore_ascii_case(kw))
+            {
+                return (false, false);
+            }
             return (true, false);
         }
         if node.divides_queries() {
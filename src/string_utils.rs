@@ -67,6 +67,67 @@ pub(crate) fn skip_string_literal(bytes: &[u8], i: usize) -> usize {
     j
 }
 
+/// Split a dollar-quoted string (`$$...$$` or `$tag$...$tag$`) into its tag
+/// and body. Returns None if `text` is not a complete dollar-quoted string.
+pub(crate) fn split_dollar_quoted(text: &str) -> Option<(&str, &str)> {
+    let bytes = text.as_bytes();
+    if bytes.first() != Some(&b'$') {
+        return None;
+    }
+    let tag_end = 1 + bytes[1..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+        .count();
+    if bytes.get(tag_end) != Some(&b'$') {
+        return None;
+    }
+    let tag = &text[..=tag_end];
+    if text.len() < 2 * tag.len() || !text.ends_with(tag) {
+        return None;
+    }
+    Some((tag, &text[tag.len()..text.len() - tag.len()]))
+}
+
+/// Rewrite a double-quoted string literal (`"it's"`) as a single-quoted one
+/// (`'it\'s'`). Doubled `""` escapes become a plain `"`, bare `'` is escaped
+/// with a backslash, and backslash escapes are kept as written. Returns None
+/// if `text` is not exactly one complete `"..."` literal (triple-quoted and
+/// prefixed literals are left alone).
+pub(crate) fn to_single_quoted(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    if bytes.len() < 2
+        || bytes[0] != b'"'
+        || bytes[bytes.len() - 1] != b'"'
+        || text.starts_with("\"\"\"")
+        || skip_string_literal(bytes, 0) != bytes.len()
+    {
+        return None;
+    }
+    let body = &text[1..text.len() - 1];
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('\'');
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                result.push(c);
+                result.push(chars.next()?);
+            }
+            '"' => {
+                // only a doubled `""` can appear inside the literal
+                if chars.next()? != '"' {
+                    return None;
+                }
+                result.push('"');
+            }
+            '\'' => result.push_str("\\'"),
+            _ => result.push(c),
+        }
+    }
+    result.push('\'');
+    Some(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +214,39 @@ mod tests {
         let pos = skip_string_literal(bytes, 0);
         assert_eq!(pos, 2, "Empty string '' should be treated as empty string");
     }
+
+    #[test]
+    fn test_split_dollar_quoted() {
+        assert_eq!(
+            split_dollar_quoted("$$ select 1 $$"),
+            Some(("$$", " select 1 "))
+        );
+        assert_eq!(
+            split_dollar_quoted("$fn$select 1$fn$"),
+            Some(("$fn$", "select 1"))
+        );
+        assert_eq!(split_dollar_quoted("$$$$"), Some(("$$", "")));
+        assert_eq!(split_dollar_quoted("$1"), None);
+        assert_eq!(split_dollar_quoted("$$"), None);
+        assert_eq!(split_dollar_quoted("$fn$ unterminated"), None);
+    }
+
+    #[test]
+    fn test_to_single_quoted() {
+        assert_eq!(to_single_quoted(r#""abc""#).as_deref(), Some("'abc'"));
+        assert_eq!(to_single_quoted(r#""""#).as_deref(), Some("''"));
+        assert_eq!(to_single_quoted(r#""it's""#).as_deref(), Some(r"'it\'s'"));
+        assert_eq!(
+            to_single_quoted(r#""say ""hi""""#).as_deref(),
+            Some(r#"'say "hi"'"#)
+        );
+        assert_eq!(
+            to_single_quoted(r#""a\"b\n""#).as_deref(),
+            Some(r#"'a\"b\n'"#)
+        );
+        assert_eq!(to_single_quoted("'abc'"), None);
+        assert_eq!(to_single_quoted(r#""""abc""""#), None);
+        assert_eq!(to_single_quoted(r#""abc"#), None);
+        assert_eq!(to_single_quoted(r#""abc\""#), None);
+    }
 }
@@ -5,7 +5,10 @@ use compact_str::CompactString;
 pub type Pos = u32;
 
 /// All token types recognized by the lexer.
-/// Mirrors Python sqlfmt's TokenType (31 variants).
+/// Mirrors Python sqlfmt's TokenType (31 variants), plus `DdlKeyword` for
+/// keywords inside CREATE TABLE column definitions, `DataType` for their
+/// column types (`decimal(12, 2)` is one token), and the `Block*` types for
+/// procedural blocks (BEGIN ... END, IF ... END IF, LOOP ... END LOOP).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TokenType {
     FmtOff,
@@ -38,6 +41,11 @@ pub enum TokenType {
     Newline,
     UntermKeyword,
     SetOperator,
+    DdlKeyword,
+    DataType,
+    BlockStart,
+    BlockKeyword,
+    BlockEnd,
     Name,
 }
 
@@ -76,8 +84,8 @@ impl TokenType {
         )
     }
 
-    /// Tokens that should be lowercased in the formatted output.
-    pub fn is_always_lowercased(self) -> bool {
+    /// Keyword tokens, whose case is set by the keyword_case option.
+    pub fn is_keyword(self) -> bool {
         matches!(
             self,
             Self::UntermKeyword
@@ -87,6 +95,10 @@ impl TokenType {
                 | Self::WordOperator
                 | Self::On
                 | Self::BooleanOperator
+                | Self::DdlKeyword
+                | Self::BlockStart
+                | Self::BlockKeyword
+                | Self::BlockEnd
         )
     }
 
@@ -125,6 +137,12 @@ impl TokenType {
         )
     }
 
+    /// Procedural block tokens that open a section of the block
+    /// (`begin`, `if`, `then`, `else`, `loop`, ...).
+    pub fn is_opening_block(self) -> bool {
+        matches!(self, Self::BlockStart | Self::BlockKeyword)
+    }
+
     pub fn is_possible_name(self) -> bool {
         matches!(self, Self::Name | Self::QuotedName | Self::Star)
     }
@@ -199,11 +217,11 @@ mod tests {
     }
 
     #[test]
-    fn test_always_lowercased() {
-        assert!(TokenType::UntermKeyword.is_always_lowercased());
-        assert!(TokenType::BooleanOperator.is_always_lowercased());
-        assert!(!TokenType::Name.is_always_lowercased());
-        assert!(!TokenType::Operator.is_always_lowercased());
+    fn test_is_keyword() {
+        assert!(TokenType::UntermKeyword.is_keyword());
+        assert!(TokenType::BooleanOperator.is_keyword());
+        assert!(!TokenType::Name.is_keyword());
+        assert!(!TokenType::Operator.is_keyword());
     }
 
     #[test]
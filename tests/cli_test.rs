@@ -369,6 +369,33 @@ fn test_invalid_dialect() {
         .code(2);
 }
 
+#[test]
+fn test_dialect_from_config() {
+    let dir = setup_temp_dir(&[(
+        "sqlfmt.toml",
+        "dialect = \"warehouse\"\n\n[dialects.warehouse]\nunterm_keywords = [\"sample by\"]\n",
+    )]);
+    let config = dir.path().join("sqlfmt.toml");
+    sqlfmt()
+        .arg("-")
+        .arg("--config")
+        .arg(&config)
+        .write_stdin("select a, b from t where a > 1 sample by 10\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\nsample by 10\n"));
+    sqlfmt()
+        .arg("-")
+        .arg("--config")
+        .arg(&config)
+        .arg("--dialect")
+        .arg("polyglot")
+        .write_stdin("select a, b from t where a > 1 sample by 10\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\nsample by 10\n").not());
+}
+
 // ─── Other CLI flags ───
 
 #[test]
@@ -0,0 +1,9 @@
+SELECT `Order ID`, Customer.`Full Name` AS customer_name, o.Total FROM `my-project.Sales.Orders` AS o JOIN `my-project`.crm.Customers AS Customer ON o.`Customer ID` = Customer.ID;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    `Order ID`
+    , customer.`Full Name` as customer_name
+    , o.total
+from `my-project.Sales.Orders` as o
+join `my-project`.crm.customers as customer on o.`Customer ID` = customer.id
+;
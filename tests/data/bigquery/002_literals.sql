@@ -0,0 +1,13 @@
+select r'\d+\.\d*' as number_pattern, R"C:\Temp" as windows_path, b'\x00\xFF' as raw_bytes, RB'''multi
+Line''' as raw_multiline, "It's" as double_quoted, 'Say "hi"' as single_quoted from t;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    r'\d+\.\d*' as number_pattern
+    , r"C:\Temp" as windows_path
+    , b'\x00\xFF' as raw_bytes
+    , rb'''multi
+Line''' as raw_multiline
+    , "It's" as double_quoted
+    , 'Say "hi"' as single_quoted
+from t
+;
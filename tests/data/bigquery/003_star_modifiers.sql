@@ -0,0 +1,35 @@
+select * except (ssn, date_of_birth) replace (upper(name) as name, round(balance, 2) as balance) from customers;
+select safe.divide(revenue, orders) as avg_order, SAFE.PARSE_DATE('%Y%m%d', day) as day, safe.replace(sku, '-', '') as sku from sales;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    * except (
+        ssn
+        , date_of_birth
+    )
+    replace (
+        upper(
+            name
+        ) as name
+        , round(
+            balance
+            , 2
+        ) as balance
+    )
+from customers
+;
+select
+    safe.divide(
+        revenue
+        , orders
+    ) as avg_order
+    , safe.parse_date(
+        '%Y%m%d'
+        , day
+    ) as day
+    , safe.replace(
+        sku
+        , '-'
+        , ''
+    ) as sku
+from sales
+;
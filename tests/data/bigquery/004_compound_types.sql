@@ -0,0 +1,18 @@
+select cast(payload as STRUCT<id INT64, tags ARRAY<STRING>>) as parsed, ARRAY<INT64>[1, 2] as ids from events;
+select struct<a int64, b array<string>>(1, []) as s from t;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    cast(payload as struct<id int64, tags array<string>>) as parsed
+    , array<int64>[
+        1
+        , 2
+    ] as ids
+from events
+;
+select
+    struct<a int64, b array<string>>(
+        1
+        , []
+    ) as s
+from t
+;
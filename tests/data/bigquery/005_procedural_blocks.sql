@@ -0,0 +1,91 @@
+DECLARE x INT64 DEFAULT 0;
+SET x = 5;
+IF x > 0 THEN
+  SELECT 'positive';
+ELSEIF x < 0 THEN
+  SELECT 'negative';
+ELSE
+  SELECT 'zero';
+END IF;
+LOOP
+  SET x = x + 1;
+  IF x >= 10 THEN LEAVE; END IF;
+END LOOP;
+WHILE x > 0 DO
+  SET x = x - 1;
+END WHILE;
+FOR rec IN (SELECT id, name FROM dataset.users WHERE active) DO
+  INSERT INTO dataset.log (id) VALUES (rec.id);
+END FOR;
+REPEAT
+  SET x = x + 1;
+  UNTIL x >= 3
+END REPEAT;
+BEGIN
+  SELECT 1/0;
+EXCEPTION WHEN ERROR THEN
+  RAISE USING MESSAGE = "division failed";
+END;
+begin transaction;
+commit;
+)))))__SQLFMT_OUTPUT__(((((
+declare x int64 default 0
+;
+set x = 5
+;
+if x > 0 then
+    select 'positive'
+    ;
+elseif x < 0 then
+    select 'negative'
+    ;
+else
+    select 'zero'
+    ;
+end if
+;
+loop
+    set x = x + 1
+    ;
+    if x >= 10 then
+        leave
+        ;
+    end if
+    ;
+end loop
+;
+while x > 0 do
+    set x = x - 1
+    ;
+end while
+;
+for rec
+    in (
+        select
+            id
+            , name
+        from dataset.users
+        where active
+    ) do
+    insert into dataset.log (id)
+    values (rec.id)
+    ;
+end for
+;
+repeat
+    set x = x + 1
+    ;
+    until x >= 3
+end repeat
+;
+begin
+    select 1 / 0
+    ;
+exception
+when error then
+    raise using message = "division failed"
+    ;
+end
+;
+begin transaction;
+commit;
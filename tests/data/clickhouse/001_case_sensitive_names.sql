@@ -0,0 +1,17 @@
+SELECT toStartOfMonth(EventDate) AS Month, countIf(IsMobile) AS MobileHits, uniqExact(UserID)
+FROM hits_v1 PREWHERE CounterID = 62 WHERE EventDate >= toDate('2024-01-01')
+GROUP BY Month ORDER BY Month;
+select toStartOfMonth(x) from t
+)))))__SQLFMT_OUTPUT__(((((
+select
+    toStartOfMonth(EventDate) as Month
+    , countIf(IsMobile) as MobileHits
+    , uniqExact(UserID)
+from hits_v1
+prewhere CounterID = 62
+where EventDate >= toDate('2024-01-01')
+group by Month
+order by Month
+;
+select toStartOfMonth(x)
+from t
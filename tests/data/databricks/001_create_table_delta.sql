@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS sales.orders (order_id BIGINT, region STRING, amount DECIMAL(10, 2)) USING DELTA PARTITIONED BY (region) LOCATION 's3://bucket/sales/orders' TBLPROPERTIES ('delta.appendOnly' = 'true');
+create or replace table ${catalog}.sales.daily using delta clustered by (region) as select region, sum(amount) as amount from sales.orders group by region;
+)))))__SQLFMT_OUTPUT__(((((
+create table if not exists
+    sales.orders (
+        order_id bigint
+        , region string
+        , amount decimal(10, 2)
+    )
+using delta
+partitioned by (region)
+location 's3://bucket/sales/orders'
+tblproperties ('delta.appendOnly' = 'true')
+;
+create or replace table ${catalog}.sales.daily
+using delta
+clustered by (region) as
+select
+    region
+    , sum(amount) as amount
+from sales.orders
+group by region
+;
@@ -0,0 +1,20 @@
+optimize sales.orders where order_date >= '2024-01-01' zorder by (customer_id);
+merge with schema evolution into sales.orders as t using updates as s on t.order_id = s.order_id when matched then update set * when not matched then insert *;
+select optimize from jobs where optimize = 1 and retries < 3 and last_run_at >= current_date() - interval 7 days;
+)))))__SQLFMT_OUTPUT__(((((
+optimize sales.orders
+where order_date >= '2024-01-01'
+zorder by (customer_id)
+;
+merge with schema evolution into sales.orders as t
+using updates as s
+on t.order_id = s.order_id
+when matched
+then update set *
+when not matched
+then insert *
+;
+select optimize
+from jobs
+where optimize = 1 and retries < 3 and last_run_at >= current_date() - interval 7 days
+;
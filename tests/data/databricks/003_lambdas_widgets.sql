@@ -0,0 +1,17 @@
+select transform(items, x -> x.price * 2) as doubled, exists(items, x -> x.qty = 0) as any_empty from ${catalog}.${schema}.orders where region = '${region}' distribute by region sort by order_id;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    transform(
+        items
+        , x
+        -> x.price * 2
+    ) as doubled
+    , exists (
+        items
+        , x -> x.qty = 0
+    ) as any_empty
+from ${catalog}.${schema}.orders
+where region = '${region}'
+distribute by region
+sort by order_id
+;
@@ -0,0 +1,19 @@
+# monthly order totals
+SELECT STRAIGHT_JOIN `o`.`customer_id`, SUM(`o`.`total`) AS `Total Spent`, "n/a" AS note -- fallback
+FROM `shop`.`orders` AS `o` STRAIGHT_JOIN `shop`.`customers` c ON c.id = o.customer_id
+WHERE o.created_at >= '2024-01-01' # inclusive
+GROUP BY 1
+ORDER BY 2 DESC
+LIMIT 20, 10
+)))))__SQLFMT_OUTPUT__(((((
+# monthly order totals
+select straight_join
+    `o`.`customer_id`
+    , sum(`o`.`total`) as `Total Spent`
+    , "n/a" as note  -- fallback
+from `shop`.`orders` as `o`
+straight_join `shop`.`customers` c on c.id = o.customer_id
+where o.created_at >= '2024-01-01'  # inclusive
+group by 1
+order by 2 desc
+limit 20, 10
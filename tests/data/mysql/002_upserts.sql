@@ -0,0 +1,34 @@
+INSERT INTO counters (id, hits, updated_at) VALUES (1, 1, NOW()) ON DUPLICATE KEY UPDATE hits = hits + VALUES(hits), updated_at = VALUES(updated_at);
+SET @rank := 0;
+SELECT @rank := @rank + 1 AS rnk, name FROM players ORDER BY score DESC LIMIT 0, 100;
+)))))__SQLFMT_OUTPUT__(((((
+insert into
+    counters (
+        id
+        , hits
+        , updated_at
+    )
+values
+    (
+        1
+        , 1
+        , now()
+    )
+on duplicate key update
+    hits
+    = hits
+    + values(hits)
+    , updated_at
+    = values(updated_at)
+;
+set @rank := 0
+;
+select
+    @rank
+    := @rank
+    + 1 as rnk
+    , name
+from players
+order by score desc
+limit 0, 100
+;
@@ -0,0 +1,36 @@
+DELIMITER //
+CREATE TRIGGER orders_bi BEFORE INSERT ON orders FOR EACH ROW SET NEW.created_at = NOW()//
+SELECT id, total FROM orders WHERE total > 100//
+DELIMITER ;
+SELECT delimiter FROM settings;
+DELIMITER $$
+CREATE PROCEDURE archive_orders(IN cutoff DATE)
+BEGIN
+    INSERT INTO orders_archive SELECT * FROM orders WHERE created_at < cutoff;
+    DELETE FROM orders WHERE created_at < cutoff;
+END$$
+DELIMITER ;
+SELECT count(*) FROM orders_archive;
+)))))__SQLFMT_OUTPUT__(((((
+delimiter //
+CREATE TRIGGER orders_bi BEFORE INSERT ON orders FOR EACH ROW SET NEW.created_at = NOW()//
+select
+    id
+    , total
+from orders
+where total > 100
+//
+delimiter ;
+select delimiter
+from settings
+;
+delimiter $$
+create procedure archive_orders(in cutoff date) begin
+    INSERT INTO orders_archive SELECT * FROM orders WHERE created_at < cutoff;
+    DELETE FROM orders WHERE created_at < cutoff;
+    end
+$$
+delimiter ;
+select count(*)
+from orders_archive
+;
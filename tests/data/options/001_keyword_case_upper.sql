@@ -0,0 +1,26 @@
+Select a, b As total, Count(*) From s.t
+Where a Is Not Null And b In (Select x From u Union All Select y From v)
+GROUP   BY a, b Order By total DESC;
+insert into t (a) values (1)
+)))))__SQLFMT_OUTPUT__(((((
+SELECT
+    a
+    , b AS total
+    , count(*)
+FROM s.t
+WHERE
+    a IS NOT null
+    AND b IN (
+        SELECT x
+        FROM u
+        UNION ALL
+        SELECT y
+        FROM v
+    )
+GROUP BY
+    a
+    , b
+ORDER BY total DESC
+;
+INSERT INTO t (a)
+VALUES (1)
@@ -0,0 +1,26 @@
+Select a, b As total, Count(*) From s.t
+Where a Is Not Null And b In (Select x From u Union All Select y From v)
+GROUP   BY a, b Order By total DESC;
+insert into t (a) values (1)
+)))))__SQLFMT_OUTPUT__(((((
+Select
+    a
+    , b As total
+    , count(*)
+From s.t
+Where
+    a Is Not null
+    And b In (
+        Select x
+        From u
+        Union All
+        Select y
+        From v
+    )
+GROUP BY
+    a
+    , b
+Order By total DESC
+;
+insert into t (a)
+values (1)
@@ -0,0 +1,13 @@
+SELECT CustomerId, Count(*) AS OrderCount, sum(o.Amount) AS Total, cast(o.Placed AS date)
+FROM Sales.Orders AS o
+WHERE o.Status = 'Open' AND o.Region IN (%(Region)s)
+GROUP BY CustomerId
+)))))__SQLFMT_OUTPUT__(((((
+select
+    customerid
+    , COUNT(*) as ordercount
+    , SUM(o.amount) as total
+    , CAST(o.placed as date)
+from sales.orders as o
+where o.status = 'Open' and o.region in (%(Region)s)
+group by customerid
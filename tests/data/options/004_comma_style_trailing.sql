@@ -0,0 +1,38 @@
+select customer_id, order_id, -- the order
+    coalesce(shipped_at, delivered_at, cancelled_at, returned_at, updated_at, created_at) as last_event_at,
+    {% if var("with_totals") %}
+    sum(amount) over (partition by customer_id, order_id) as total,
+    {% endif %}
+    -- trailing column
+    status
+from orders, lateral flatten(input => orders.items) as item
+group by customer_id, order_id, status
+)))))__SQLFMT_OUTPUT__(((((
+select
+    customer_id,
+    order_id,  -- the order
+    coalesce(
+        shipped_at,
+        delivered_at,
+        cancelled_at,
+        returned_at,
+        updated_at,
+        created_at
+    ) as last_event_at,
+    {% if var("with_totals") %}
+        sum(amount) over (
+            partition by
+                customer_id,
+                order_id
+        ) as total,
+    {% endif %}
+    -- trailing column
+    status
+from
+    orders,
+    lateral
+    flatten(input => orders.items) as item
+group by
+    customer_id,
+    order_id,
+    status
@@ -0,0 +1,37 @@
+{{ config(materialized="incremental", unique_key="order_id", on_schema_change="append_new_columns") }}
+with orders as (
+    select order_id, customer_id, case when status = 'shipped' then shipped_at else updated_at end as event_at
+    from {{ ref("stg_orders") }}
+    -- only keep orders placed after the cutover date, since older rows come from the legacy system and are reloaded separately
+    where placed_at >= '2020-01-01' and (status in ('shipped', 'delivered') or returned_at is not null)
+)
+select * from orders
+)))))__SQLFMT_OUTPUT__(((((
+{{
+  config(
+    materialized="incremental",
+    unique_key="order_id",
+    on_schema_change="append_new_columns",
+  )
+}}
+with
+  orders as (
+    select
+      order_id
+      , customer_id
+      , case when status = 'shipped' then shipped_at else updated_at end as event_at
+    from {{ ref("stg_orders") }}
+    -- only keep orders placed after the cutover date, since older rows come from the
+    -- legacy system and are reloaded separately
+    where
+      placed_at >= '2020-01-01'
+      and (
+        status in (
+          'shipped'
+          , 'delivered'
+        )
+        or returned_at is not null
+      )
+  )
+select *
+from orders
@@ -0,0 +1,37 @@
+select
+    o.order_id
+    , o.customer_id as cust -- who ordered
+    , o.amount_usd as total
+    , cast(o.placed_at as date) as placed_on -- local date
+    , case when o.status = 'shipped' then 1 else 0 end as is_shipped  -- flag
+    , count(*) over (partition by o.customer_id, o.region, o.channel, o.segment order by o.placed_at) as n
+    , o.notes
+    , b as "Quoted Alias"
+from orders as o
+join (select id as customer_id, name as customer_name, region_code as region from customers) as c on o.customer_id = c.customer_id
+)))))__SQLFMT_OUTPUT__(((((
+select
+    o.order_id
+    , o.customer_id                                    as cust        -- who ordered
+    , o.amount_usd                                     as total
+    , cast(o.placed_at as date)                        as placed_on   -- local date
+    , case when o.status = 'shipped' then 1 else 0 end as is_shipped  -- flag
+    , count(*) over (
+        partition by
+            o.customer_id
+            , o.region
+            , o.channel
+            , o.segment
+        order by o.placed_at
+    ) as n
+    , o.notes
+    , b as "Quoted Alias"
+from orders as o
+join
+    (
+        select
+            id            as customer_id
+            , name        as customer_name
+            , region_code as region
+        from customers
+    ) as c on o.customer_id = c.customer_id
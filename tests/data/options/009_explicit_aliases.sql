@@ -0,0 +1,31 @@
+select o.id order_id, c.name "Customer Name", count(*) n, case when o.total > 100 then 'large' else 'small' end size_bucket
+    , date '2024-01-01' cutoff, x::double precision, sum(o.total) as total
+from {{ ref("orders") }} o
+join customers c on o.customer_id = c.id
+left join (select customer_id, max(placed_at) last_order_at from orders group by customer_id) lo using (customer_id)
+group by 1, 2, 4, 5, 6
+)))))__SQLFMT_OUTPUT__(((((
+select
+    o.id as order_id
+    , c.name as "Customer Name"
+    , count(*) as n
+    , case when o.total > 100 then 'large' else 'small' end as size_bucket
+    , date '2024-01-01' as cutoff
+    , x::double precision
+    , sum(o.total) as total
+from {{ ref("orders") }} as o
+join customers as c on o.customer_id = c.id
+left join
+    (
+        select
+            customer_id
+            , max(placed_at) as last_order_at
+        from orders
+        group by customer_id
+    ) as lo using (customer_id)
+group by
+    1
+    , 2
+    , 4
+    , 5
+    , 6
@@ -0,0 +1,16 @@
+select a != b as ne, c<>d as ne2, e == f as eq, first_name || ' ' || last_name as full_name
+from t
+where status != 'closed' and {{ var("x") != 1 }}
+)))))__SQLFMT_OUTPUT__(((((
+select
+    a
+    <> b as ne
+    , c
+    <> d as ne2
+    , e
+    = f as eq
+    , first_name
+    || ' '
+    || last_name as full_name
+from t
+where status <> 'closed' and {{ var("x") != 1 }}
@@ -0,0 +1,18 @@
+select "active" as status, "it's" as contraction, "say ""hi""" as doubled, "a \"b\"" as escaped
+from `my-project.dataset.events`
+where country in ("US", 'CA') and note != "" and pattern = r"raw\d+" and body = """triple"""
+)))))__SQLFMT_OUTPUT__(((((
+select
+    'active' as status
+    , 'it\'s' as contraction
+    , 'say "hi"' as doubled
+    , 'a \"b\"' as escaped
+from `my-project.dataset.events`
+where
+    country in (
+        'US'
+        , 'CA'
+    )
+    and note != ''
+    and pattern = r"raw\d+"
+    and body = """triple"""
@@ -0,0 +1,21 @@
+select order_id, (quantity * unit_price) as gross, (gross - discount) - tax as net, count((customer_id)) as customers
+from orders join customers on (orders.customer_id = customers.id)
+where ((status = 'shipped') and ((region = 'emea') or (region = 'apac'))) and total - (refund - credit) > 0
+    and not exists (select 1 from returns where returns.order_id = orders.order_id) and (a or b) and c
+)))))__SQLFMT_OUTPUT__(((((
+select
+    order_id
+    , quantity
+    * unit_price as gross
+    , (gross - discount)
+    - tax as net
+    , count(customer_id) as customers
+from orders
+join customers on orders.customer_id = customers.id
+where
+    status = 'shipped'
+    and (region = 'emea' or region = 'apac')
+    and total - (refund - credit) > 0
+    and not exists (select 1 from returns where returns.order_id = orders.order_id)
+    and (a or b)
+    and c
@@ -0,0 +1,40 @@
+select id, payload #>> '{customer,name}' as customer_name, payload -> 'items' as items, tags from orders where payload @> '{"status": "shipped"}' and tags && array['priority', 'gift'] and payload ?| array['coupon', 'discount'] and not payload ? 'refunded';
+SELECT name FROM users WHERE email ~* '@example\.com$' AND name !~~ 'test%' AND name ~~* 'a%' AND ip <<= inet '10.0.0.0/8' AND flags # 4 = 0;
+select |/ area as side, tsv @@ to_tsquery('fmt & sql') as hit from shapes where title ^@ 'sql' and ||/ volume > 2;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    id
+    , payload
+    #>> '{customer,name}' as customer_name
+    , payload
+    -> 'items' as items
+    , tags
+from orders
+where
+    payload @> '{"status": "shipped"}'
+    and tags && array[
+        'priority'
+        , 'gift'
+    ]
+    and payload ?| array[
+        'coupon'
+        , 'discount'
+    ]
+    and not payload ? 'refunded'
+;
+select name
+from users
+where
+    email ~* '@example\.com$'
+    and name !~~ 'test%'
+    and name ~~* 'a%'
+    and ip <<= inet '10.0.0.0/8'
+    and flags # 4 = 0
+;
+select
+    |/ area as side
+    , tsv
+    @@ to_tsquery('fmt & sql') as hit
+from shapes
+where title ^@ 'sql' and ||/ volume > 2
+;
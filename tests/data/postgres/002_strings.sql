@@ -0,0 +1,18 @@
+select 'C:\' as windows_path, E'Line One\nLine Two' as escaped, e'it\'s' as quoted, U&'d\0061t\+000061' as unicode, 'it''s' as doubled from t;
+create function add_one(x integer) returns integer as $fn$ select x+1 $fn$ language sql;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    'C:\' as windows_path
+    , e'Line One\nLine Two' as escaped
+    , e'it\'s' as quoted
+    , u&'d\0061t\+000061' as unicode
+    , 'it''s' as doubled
+from t
+;
+create function add_one(x integer)
+returns integer
+as $fn$
+    select x + 1
+$fn$
+language sql
+;
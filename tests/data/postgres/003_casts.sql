@@ -0,0 +1,12 @@
+select ids::int[] as ids, names::varchar(64)[] as names, raw::text[]::int[] as parsed, created_at::timestamp with time zone as created_at, opened::time without time zone as opened, '{1,2,3}'::int[] @> array[2] as has_two from events;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    ids::int[] as ids
+    , names::varchar(64)[] as names
+    , raw::text[]::int[] as parsed
+    , created_at::timestamp with time zone as created_at
+    , opened::time without time zone as opened
+    , '{1,2,3}'::int[]
+    @> array[2] as has_two
+from events
+;
@@ -0,0 +1,16 @@
+SELECT "OrderId", CustomerName, COUNT(*) AS OrderCount FROM Sales.Orders
+WHERE Status IN ('Open', 'Held') GROUP BY "OrderId", CustomerName
+)))))__SQLFMT_OUTPUT__(((((
+select
+    "OrderId"
+    , CustomerName
+    , COUNT(*) as OrderCount
+from Sales.Orders
+where
+    Status in (
+        'Open'
+        , 'Held'
+    )
+group by
+    "OrderId"
+    , CustomerName
@@ -0,0 +1,22 @@
+SELECT v:customer.name::STRING AS name, v:"Items"[0]:sku::VARCHAR AS sku, src:a.b[1] AS nested, metadata$filename AS file_name
+FROM raw_events QUALIFY ROW_NUMBER() OVER (PARTITION BY v:id ORDER BY v:ts::TIMESTAMP_NTZ DESC) = 1;
+select * from identifier('my_db.my_schema.' || $table_name) where id = $id;
+select $$it's a "string"$$ as s, system$typeof(v) as t from identifier($my_table);
+)))))__SQLFMT_OUTPUT__(((((
+select
+    v:customer.name::string as name
+    , v:"Items"[0]:sku::varchar as sku
+    , src:a.b[1] as nested
+    , metadata$filename as file_name
+from raw_events
+qualify row_number() over (partition by v:id order by v:ts::timestamp_ntz desc) = 1
+;
+select *
+from identifier('my_db.my_schema.' || $table_name)
+where id = $id
+;
+select
+    $$it's a "string"$$ as s
+    , system$typeof(v) as t
+from identifier($my_table)
+;
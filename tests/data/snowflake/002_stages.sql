@@ -0,0 +1,55 @@
+select $1, $2:name::string, metadata$file_row_number from @my_stage/path/ (file_format => 'my_json') t;
+select * from @~/staged;
+select * from @Raw.Landing/Events/2024/ (pattern => '.*[.]JSON');
+list @%my_table;
+copy into my_table from @my_stage/data/2024/ file_format = (type = 'csv' skip_header = 1) pattern = '.*[.]csv' on_error = 'continue';
+COPY INTO my_table (id, name) FROM (SELECT t.$1, t.$2 FROM @db.sch.stage t) FILES = ('a.csv', 'b.csv') FORCE = TRUE;
+copy into @my_stage/unload/ from (select * from my_table where d > '2024-01-01') partition by ('d=' || d) file_format = (type = parquet) header = true max_file_size = 32000000;
+put file:///tmp/data.csv @my_stage auto_compress = true;
+)))))__SQLFMT_OUTPUT__(((((
+select
+    $1
+    , $2:name::string
+    , metadata$file_row_number
+from @my_stage/path/ (file_format => 'my_json') t
+;
+select *
+from @~/staged
+;
+select *
+from @Raw.Landing/Events/2024/ (pattern => '.*[.]JSON')
+;
+list @%my_table;
+copy into my_table
+from @my_stage/data/2024/
+file_format = (type = 'csv' skip_header = 1)
+pattern = '.*[.]csv'
+on_error = 'continue'
+;
+copy into
+    my_table (
+        id
+        , name
+    )
+from
+    (
+        select
+            t.$1
+            , t.$2
+        from @db.sch.stage t
+    )
+files
+    = (
+        'a.csv'
+        , 'b.csv'
+    )
+force = true
+;
+copy into @my_stage/unload/
+from (select * from my_table where d > '2024-01-01')
+partition by ('d=' || d)
+file_format = (type = parquet)
+header = true
+max_file_size = 32000000
+;
+put file:///tmp/data.csv @my_stage auto_compress = true;
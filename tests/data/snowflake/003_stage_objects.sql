@@ -0,0 +1,40 @@
+create or replace stage my_stage url = 's3://bucket/path/' storage_integration = s3_int file_format = (type = 'json');
+create pipe if not exists my_pipe auto_ingest = true as copy into my_table from @my_stage file_format = (type = 'json');
+create stream my_stream on table my_table append_only = true comment = 'new rows';
+create task my_task warehouse = my_wh schedule = 'USING CRON 0 * * * * UTC' when system$stream_has_data('my_stream') as insert into target select * from my_stream;
+create task child_task after my_task as merge into t using s on t.id = s.id when matched then update set t.v = s.v;
+)))))__SQLFMT_OUTPUT__(((((
+create or replace stage my_stage
+url = 's3://bucket/path/'
+storage_integration = s3_int
+file_format = (type = 'json')
+;
+create pipe if not exists my_pipe
+auto_ingest = true
+as
+copy into my_table
+from @my_stage
+file_format = (type = 'json')
+;
+create stream my_stream on table my_table
+append_only = true
+comment = 'new rows'
+;
+create task my_task
+warehouse = my_wh
+schedule = 'USING CRON 0 * * * * UTC'
+when system$stream_has_data('my_stream')
+as
+insert into target
+select *
+from my_stream
+;
+create task child_task
+after my_task
+as
+merge into t
+using s
+on t.id = s.id
+when matched
+then update set t.v = s.v
+;
@@ -0,0 +1,21 @@
+show tables in schema my_db.my_schema ->> select "name", "rows" from $1 where "rows" > 0 order by "rows" desc;
+select * from a ->> select count(*) from $1 ->> select * from $2;
+)))))__SQLFMT_OUTPUT__(((((
+show tables in schema my_db.my_schema
+->>
+select
+    "name"
+    , "rows"
+from $1
+where "rows" > 0
+order by "rows" desc
+;
+select *
+from a
+->>
+select count(*)
+from $1
+->>
+select *
+from $2
+;
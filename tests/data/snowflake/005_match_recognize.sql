@@ -0,0 +1,28 @@
+select * from stock_price_history match_recognize (partition by company order by price_date measures match_number() as match_number, first(price_date) as start_date, last(price_date) as end_date one row per match after match skip past last row pattern (down+ up+) define down as price < lag(price)) order by company, match_number;
+select pattern, define from patterns where pattern like 'a%';
+)))))__SQLFMT_OUTPUT__(((((
+select *
+from stock_price_history
+match_recognize
+    (
+        partition by company
+        order by price_date
+        measures
+            match_number() as match_number
+            , first(price_date) as start_date
+            , last(price_date) as end_date
+        one row per match
+        after match skip past last row
+        pattern (down+ up+)
+        define down as price < lag(price)
+    )
+order by
+    company
+    , match_number
+;
+select
+    pattern
+    , define
+from patterns
+where pattern like 'a%'
+;
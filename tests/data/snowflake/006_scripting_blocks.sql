@@ -0,0 +1,85 @@
+EXECUTE IMMEDIATE $$
+DECLARE
+  profit NUMBER(38, 2) DEFAULT 0.0;
+  c1 CURSOR FOR SELECT price FROM invoices;
+BEGIN
+  LET cost NUMBER(38, 2) := 100.0;
+  FOR rec IN c1 DO
+    profit := profit + rec.price;
+  END FOR;
+  IF (profit > cost) THEN
+    RETURN 'profit';
+  ELSE
+    RETURN 'loss';
+  END IF;
+EXCEPTION
+  WHEN statement_error THEN
+    RETURN OBJECT_CONSTRUCT('error', sqlerrm);
+  WHEN OTHER THEN
+    RAISE;
+END;
+$$;
+
+EXECUTE IMMEDIATE $$
+DECLARE x INT DEFAULT 0; BEGIN x := x + 1; RETURN x; END;
+$$;
+)))))__SQLFMT_OUTPUT__(((((
+execute immediate $$
+    declare
+        profit number(
+            38
+            , 2
+        ) default 0.0
+        ;
+        c1 cursor for
+        select price
+        from invoices
+        ;
+    begin
+        let cost number(
+            38
+            , 2
+        )
+        := 100.0
+        ;
+        for rec in c1 do
+            profit := profit + rec.price
+            ;
+        end for
+        ;
+        if (profit > cost) then
+            return 'profit'
+            ;
+        else
+            return 'loss'
+            ;
+        end if
+        ;
+    exception
+    when statement_error then
+        return object_construct(
+            'error'
+            , sqlerrm
+        )
+        ;
+    when other then
+        raise
+        ;
+    end
+    ;
+$$
+;
+
+execute immediate $$
+    declare
+        x int default 0
+        ;
+    begin
+        x := x + 1
+        ;
+        return x
+        ;
+    end
+    ;
+$$
+;
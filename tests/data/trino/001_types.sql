@@ -0,0 +1,31 @@
+SELECT CAST(ROW(1, 'a') AS ROW(id INTEGER, name VARCHAR)) AS r, CAST(MAP(ARRAY['a'], ARRAY[1]) AS MAP(VARCHAR, INTEGER)) AS m, CAST(json_parse(payload) AS ARRAY(MAP(VARCHAR, VARCHAR))) AS items FROM "Lake"."Orders";
+)))))__SQLFMT_OUTPUT__(((((
+select
+    cast(
+        row(
+            1
+            , 'a'
+        ) as row(
+            id integer
+            , name varchar
+        )
+    ) as r
+    , cast(
+        map(
+            array['a']
+            , array[1]
+        ) as map(
+            varchar
+            , integer
+        )
+    ) as m
+    , cast(
+        json_parse(payload) as array(
+            map(
+                varchar
+                , varchar
+            )
+        )
+    ) as items
+from "Lake"."Orders"
+;
@@ -0,0 +1,19 @@
+select o.id, t.item, t.idx, transform(o.prices, p -> p * 1.1) as marked_up from orders as o cross join unnest(o.items) with ordinality as t(item, idx) where "Region" = 'EMEA' and path like 'C:\%';
+)))))__SQLFMT_OUTPUT__(((((
+select
+    o.id
+    , t.item
+    , t.idx
+    , transform(
+        o.prices
+        , p
+        -> p * 1.1
+    ) as marked_up
+from orders as o
+cross join
+    unnest(o.items) with ordinality as t(
+        item
+        , idx
+    )
+where "Region" = 'EMEA' and path like 'C:\%'
+;
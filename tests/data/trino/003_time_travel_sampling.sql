@@ -0,0 +1,14 @@
+select * from iceberg.sales.orders for timestamp as of timestamp '2024-01-01 00:00:00 UTC' where id > 0;
+select * from iceberg.sales.orders for version as of 8954597067493422955;
+select count(*) from iceberg.sales.orders tablesample bernoulli (10);
+)))))__SQLFMT_OUTPUT__(((((
+select *
+from iceberg.sales.orders for timestamp as of timestamp '2024-01-01 00:00:00 UTC'
+where id > 0
+;
+select *
+from iceberg.sales.orders for version as of 8954597067493422955
+;
+select count(*)
+from iceberg.sales.orders tablesample bernoulli(10)
+;
@@ -0,0 +1,20 @@
+SELECT TOP (10) [Order ID], o.[Customer Name], [dbo].[fn_total](o.[Order ID]) AS [Total]]Value], @@ROWCOUNT AS rc
+FROM [dbo].[Orders] AS o
+JOIN [Sales].[Customers] c ON c.[Customer ID] = o.[Customer ID]
+WHERE o.[Total] > @min_total AND o.[Status] IN ('open', 'held')
+ORDER BY o.[Total] DESC
+)))))__SQLFMT_OUTPUT__(((((
+select top (10)
+    [Order ID]
+    , o.[Customer Name]
+    , [dbo].[fn_total](o.[Order ID]) as [Total]]Value]
+    , @@rowcount as rc
+from [dbo].[Orders] as o
+join [Sales].[Customers] c on c.[Customer ID] = o.[Customer ID]
+where
+    o.[Total] > @min_total
+    and o.[Status] in (
+        'open'
+        , 'held'
+    )
+order by o.[Total] desc
@@ -0,0 +1,42 @@
+DECLARE @n INT = 5;
+SET @n = @n + 1;
+GO
+INSERT INTO dbo.audit (id, note) OUTPUT inserted.id, inserted.note VALUES (1, 'created');
+UPDATE dbo.orders SET status = 'done' OUTPUT deleted.status, inserted.status WHERE id = @id;
+GO
+SELECT TOP 5 PERCENT WITH TIES id, total FROM dbo.orders ORDER BY total DESC
+go 2
+)))))__SQLFMT_OUTPUT__(((((
+declare @n int = 5
+;
+set @n = @n + 1
+;
+go
+insert into
+    dbo.audit (
+        id
+        , note
+    )
+output
+    inserted.id
+    , inserted.note
+values
+    (
+        1
+        , 'created'
+    )
+;
+update dbo.orders
+set status = 'done'
+output
+    deleted.status
+    , inserted.status
+where id = @id
+;
+go
+select top 5 percent with ties
+    id
+    , total
+from dbo.orders
+order by total desc
+go 2
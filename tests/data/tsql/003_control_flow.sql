@@ -0,0 +1,51 @@
+IF @n > 0
+BEGIN
+    SELECT 1 AS a
+    UPDATE t SET x = 1 WHERE y = 2
+END
+ELSE
+BEGIN
+    PRINT 'none'
+END
+WHILE @i < 10 BEGIN SET @i = @i + 1 END
+BEGIN TRAN
+    UPDATE dbo.orders SET status = 'x' WHERE id = 1
+    IF @@ERROR <> 0 ROLLBACK TRAN
+COMMIT TRAN
+WHILE @i < 10 SET @i = @i + 1
+IF @n > 0 PRINT 'some' ELSE PRINT 'none'
+IF EXISTS (SELECT 1 FROM t WHERE a = 1) DELETE FROM t WHERE a = 1
+SELECT 1
+)))))__SQLFMT_OUTPUT__(((((
+if @n > 0
+begin
+    select 1 as a
+    update t
+    set x = 1
+    where y = 2
+end
+else
+begin
+    print 'none'
+end
+while @i < 10
+begin
+    set @i = @i + 1
+end
+begin tran
+    update dbo.orders
+    set status = 'x'
+    where id = 1
+    if @@error <> 0
+        rollback tran
+commit tran
+while @i < 10
+    set @i = @i + 1
+if @n > 0
+    print 'some'
+else
+    print 'none'
+if exists (select 1 from t where a = 1)
+    delete from t
+    where a = 1
+select 1
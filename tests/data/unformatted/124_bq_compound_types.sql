@@ -22,8 +22,7 @@ select
         "Nathan" as name
         , array<float64>[] as laps
     )
-    , struct<int32
-        , int64>(
+    , struct<int32, int64>(
         1
         , 2
     )
@@ -46,15 +45,6 @@ select
         , '3'
     ] as strings
 ;
-create function
-    foo(
-        bar array<struct<string
-                , array<bytes(5)>
-                , int64>>
-    )
-returns
-    array<struct<
-            string
-            , array<bytes(5)>
-            , int64>>
+create function foo(bar array<struct<string, array<bytes(5)>, int64>>)
+returns array<struct<string, array<bytes(5)>, int64>>
 as bar
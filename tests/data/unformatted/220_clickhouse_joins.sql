@@ -70,7 +70,7 @@ from table_1
 asof join table_2 using
     (
         equi_column1
-        , equi_columnn
+        , equi_columnN
         , asof_column
     )
 ;
@@ -83,8 +83,8 @@ paste join (select number as a from numbers(2) order by a desc) as t2
 select
     a
     , b
-    , totypename(a)
-    , totypename(b)
+    , toTypeName(a)
+    , toTypeName(b)
 from t_1
 full join t_2 using
     (
@@ -93,36 +93,36 @@ full join t_2 using
     )
 ;
 
-select uniq(userid)
+select uniq(UserID)
 from distributed_table
 where
-    counterid = 101500
-    and userid global in (select userid from distributed_table where counterid = 34)
+    CounterID = 101500
+    and UserID global in (select UserID from distributed_table where CounterID = 34)
 ;
 
 select
-    counterid
+    CounterID
     , hits
     , visits
 from
     (
         select
-            counterid
+            CounterID
             ,
             count() as hits
         from test.hits
-        group by counterid
+        group by CounterID
     )
 any left join
     (
         select
-            counterid
+            CounterID
             ,
-            sum(sign) as visits
+            sum(Sign) as visits
         from test.visits
-        group by counterid
+        group by CounterID
     )
-using counterid
+using CounterID
 order by hits desc
 limit 10
 ;
@@ -84,13 +84,23 @@ create function
         , out f1 int
         , out f2 text
     )
-as $$ SELECT $1, CAST($1 AS text) || ' is text' $$
+as $$
+    select
+        $1
+        , cast($1 as text)
+        || ' is text'
+$$
 language sql
 ;
 
 create function dup(int)
 returns dup_result
-as $$ SELECT $1, CAST($1 AS text) || ' is text' $$
+as $$
+    select
+        $1
+        , cast($1 as text)
+        || ' is text'
+$$
 language sql
 ;
 
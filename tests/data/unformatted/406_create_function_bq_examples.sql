@@ -95,10 +95,7 @@ group by
 ;
 
 create or replace table function mydataset.names_by_year(y int64)
-returns
-    table<name string
-        , year int64
-        , total int64>
+returns table<name string, year int64, total int64>
 as
 select
     year
@@ -0,0 +1,67 @@
+INSERT INTO my_schema.my_table (id, name, created_at) SELECT id, name, current_timestamp FROM staging.source WHERE id > 10;
+insert into t (a, b) values (1, 2), (3, 4);
+INSERT OVERWRITE TABLE events PARTITION (dt = '2024-01-01') SELECT * FROM raw_events;
+insert into some_really_long_schema_name.some_really_long_table_name (first_column, second_column, third_column, fourth_column) select 1, 2, 3, 4;
+insert into t (id, v) values (1, 'a') on conflict (id) do update set v = excluded.v;
+insert into t default values
+)))))__SQLFMT_OUTPUT__(((((
+insert into
+    my_schema.my_table (
+        id
+        , name
+        , created_at
+    )
+select
+    id
+    , name
+    , current_timestamp
+from staging.source
+where id > 10
+;
+insert into
+    t (
+        a
+        , b
+    )
+values
+    (
+        1
+        , 2
+    )
+    , (
+        3
+        , 4
+    )
+;
+insert overwrite table events partition (dt = '2024-01-01')
+select *
+from raw_events
+;
+insert into
+    some_really_long_schema_name.some_really_long_table_name (
+        first_column
+        , second_column
+        , third_column
+        , fourth_column
+    )
+select
+    1
+    , 2
+    , 3
+    , 4
+;
+insert into
+    t (
+        id
+        , v
+    )
+values
+    (
+        1
+        , 'a'
+    )
+on conflict (id)
+do update set v = excluded.v
+;
+insert into t
+default values
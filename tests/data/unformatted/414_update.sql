@@ -0,0 +1,27 @@
+UPDATE my_schema.accounts SET balance = balance + 100, updated_at = current_timestamp WHERE id = 1;
+update orders o set status = s.status, shipped_at = s.shipped_at, carrier_name = s.carrier_name from shipments s where o.id = s.order_id and s.status is not null returning o.id;
+update t set x = 1
+)))))__SQLFMT_OUTPUT__(((((
+update my_schema.accounts
+set
+    balance
+    = balance
+    + 100
+    , updated_at
+    = current_timestamp
+where id = 1
+;
+update orders o
+set
+    status
+    = s.status
+    , shipped_at
+    = s.shipped_at
+    , carrier_name
+    = s.carrier_name
+from shipments s
+where o.id = s.order_id and s.status is not null
+returning o.id
+;
+update t
+set x = 1
@@ -0,0 +1,70 @@
+MERGE INTO analytics.customers AS t USING (SELECT id, name, email, updated_at FROM staging.customers WHERE updated_at > current_date - 1) AS s ON t.id = s.id
+WHEN MATCHED AND s.is_deleted THEN DELETE
+WHEN MATCHED THEN UPDATE SET name = s.name, email = s.email, updated_at = s.updated_at
+WHEN NOT MATCHED THEN INSERT (id, name, email, updated_at) VALUES (s.id, s.name, s.email, s.updated_at);
+merge into dim_users d using (select u.id, a.email from users u join accounts a on u.id = a.user_id) src on d.id = src.id
+when matched then update set email = src.email
+when not matched by source then delete;
+merge into t using s on t.id = s.id when matched then update set v = case when s.v is null then t.v else s.v end
+when not matched then do nothing
+)))))__SQLFMT_OUTPUT__(((((
+merge into analytics.customers as t
+using
+    (
+        select
+            id
+            , name
+            , email
+            , updated_at
+        from staging.customers
+        where updated_at > current_date - 1
+    ) as s
+on t.id = s.id
+when matched and s.is_deleted
+then delete
+when matched
+then update set
+    name
+    = s.name
+    , email
+    = s.email
+    , updated_at
+    = s.updated_at
+when not matched
+then insert
+    (
+        id
+        , name
+        , email
+        , updated_at
+    )
+values
+    (
+        s.id
+        , s.name
+        , s.email
+        , s.updated_at
+    )
+;
+merge into dim_users d
+using
+    (
+        select
+            u.id
+            , a.email
+        from users u
+        join accounts a on u.id = a.user_id
+    ) src
+on d.id = src.id
+when matched
+then update set email = src.email
+when not matched by source
+then delete
+;
+merge into t
+using s
+on t.id = s.id
+when matched
+then update set v = case when s.v is null then t.v else s.v end
+when not matched
+then do nothing
@@ -0,0 +1,65 @@
+CREATE TABLE films (
+    code        char(5) CONSTRAINT firstkey PRIMARY KEY,
+    title       varchar(40) NOT NULL,
+    did         integer NOT NULL,
+    date_prod   date,
+    kind        varchar(10),
+    len         interval hour to minute
+);
+create or replace transient table if not exists analytics.orders (id number(38, 0) not null autoincrement, customer_id number references analytics.customers (id) on delete cascade, amount decimal(12, 2) default 0, created_at timestamp with time zone default current_timestamp(), note varchar comment 'free text', constraint orders_pk primary key (id), unique (customer_id, created_at), check (amount >= 0)) cluster by (created_at);
+CREATE TEMPORARY TABLE IF NOT EXISTS "staging"."Line Items" (order_id BIGINT NOT NULL REFERENCES orders (id), line_no INT GENERATED ALWAYS AS IDENTITY, sku TEXT COLLATE "C", PRIMARY KEY (order_id, line_no), FOREIGN KEY (sku) REFERENCES products (sku) ON UPDATE NO ACTION) ON COMMIT DROP;
+CREATE TABLE db.events (id UInt64 NOT NULL, name String DEFAULT 'x', ts DateTime64(3) COMMENT 'event time') ENGINE = MergeTree ORDER BY ts;
+create table t (a int, b text)
+)))))__SQLFMT_OUTPUT__(((((
+create table
+    films (
+        code char(5) constraint firstkey primary key
+        , title varchar(40) not null
+        , did integer not null
+        , date_prod date
+        , kind varchar(10)
+        , len interval hour to minute
+    )
+;
+create or replace transient table if not exists
+    analytics.orders (
+        id number(38, 0) not null autoincrement
+        , customer_id number references analytics.customers (id) on delete cascade
+        , amount decimal(12, 2) default 0
+        , created_at timestamp with time zone default current_timestamp()
+        , note varchar comment 'free text'
+        , constraint orders_pk primary key (id)
+        , unique (
+            customer_id
+            , created_at
+        )
+        , check (amount >= 0)
+    )
+cluster by (created_at)
+;
+create temporary table if not exists
+    "staging"."Line Items" (
+        order_id bigint not null references orders (id)
+        , line_no int generated always as identity
+        , sku text collate "C"
+        , primary key (
+            order_id
+            , line_no
+        )
+        , foreign key (sku) references products (sku) on update no action
+    ) on commit drop
+;
+create table
+    db.events (
+        id uint64 not null
+        , name string default 'x'
+        , ts datetime64(3) comment 'event time'
+    )
+engine = mergetree
+order by ts
+;
+create table
+    t (
+        a int
+        , b text
+    )
@@ -0,0 +1,53 @@
+CREATE OR REPLACE PROCEDURE refresh_daily_revenue()
+RETURNS VARCHAR
+LANGUAGE SQL
+EXECUTE AS CALLER
+AS $$
+DELETE FROM analytics.daily_revenue WHERE revenue_date = current_date;
+INSERT INTO analytics.daily_revenue SELECT order_date AS revenue_date, SUM(amount) AS revenue FROM raw.orders WHERE order_date = current_date GROUP BY 1;
+RETURN 'ok';
+$$;
+
+create function active_users(since date) returns setof users as $body$ select * from users where last_seen >= since and not is_deleted $body$ language sql stable;
+
+create function js_add(a float, b float) returns float language javascript as $$ return A + B; $$;
+)))))__SQLFMT_OUTPUT__(((((
+create or replace procedure refresh_daily_revenue()
+returns varchar
+language sql
+execute as caller
+as $$
+    delete from analytics.daily_revenue
+    where revenue_date = current_date
+    ;
+    insert into analytics.daily_revenue
+    select
+        order_date as revenue_date
+        , sum(amount) as revenue
+    from raw.orders
+    where order_date = current_date
+    group by 1
+    ;
+    return 'ok'
+    ;
+$$
+;
+
+create function active_users(since date)
+returns setof users
+as $body$
+    select * from users where last_seen >= since and not is_deleted
+$body$
+language sql
+stable
+;
+
+create function
+    js_add(
+        a float
+        , b float
+    )
+returns float
+language javascript
+as $$ return A + B; $$
+;
@@ -1,3 +1,4 @@
+use sqlfmt::mode::{Case, CommaStyle, IndentStyle, NormalizeOperators, NotEqual};
 use sqlfmt::{format_string, Mode};
 use std::fs;
 
@@ -76,6 +77,144 @@ fn clickhouse_mode() -> Mode {
     }
 }
 
+fn snowflake_mode() -> Mode {
+    Mode {
+        dialect_name: "snowflake".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn postgres_mode() -> Mode {
+    Mode {
+        dialect_name: "postgres".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn postgres_case_sensitive_mode() -> Mode {
+    Mode {
+        case_sensitive_names: Some(true),
+        ..postgres_mode()
+    }
+}
+
+fn bigquery_mode() -> Mode {
+    Mode {
+        dialect_name: "bigquery".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn databricks_mode() -> Mode {
+    Mode {
+        dialect_name: "databricks".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn trino_mode() -> Mode {
+    Mode {
+        dialect_name: "trino".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn tsql_mode() -> Mode {
+    Mode {
+        dialect_name: "tsql".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn mysql_mode() -> Mode {
+    Mode {
+        dialect_name: "mysql".to_string(),
+        ..Mode::default()
+    }
+}
+
+fn keyword_case_upper_mode() -> Mode {
+    Mode {
+        keyword_case: Case::Upper,
+        ..Mode::default()
+    }
+}
+
+fn keyword_case_preserve_mode() -> Mode {
+    Mode {
+        keyword_case: Case::Preserve,
+        ..Mode::default()
+    }
+}
+
+fn function_case_upper_mode() -> Mode {
+    Mode {
+        identifier_case: Some(Case::Lower),
+        function_case: Some(Case::Upper),
+        ..Mode::default()
+    }
+}
+
+fn comma_style_trailing_mode() -> Mode {
+    Mode {
+        comma_style: CommaStyle::Trailing,
+        ..Mode::default()
+    }
+}
+
+fn indent_width_2_mode() -> Mode {
+    Mode {
+        indent_width: 2,
+        ..Mode::default()
+    }
+}
+
+fn indent_style_tabs_mode() -> Mode {
+    Mode {
+        indent_style: IndentStyle::Tabs,
+        ..Mode::default()
+    }
+}
+
+fn align_aliases_mode() -> Mode {
+    Mode {
+        align_aliases: true,
+        ..Mode::default()
+    }
+}
+
+fn explicit_aliases_mode() -> Mode {
+    Mode {
+        explicit_aliases: true,
+        ..Mode::default()
+    }
+}
+
+fn normalize_operators_mode() -> Mode {
+    Mode {
+        normalize_operators: NormalizeOperators {
+            not_equal: Some(NotEqual::AngleBrackets),
+            double_equals: true,
+        },
+        ..Mode::default()
+    }
+}
+
+fn single_quote_strings_mode() -> Mode {
+    Mode {
+        dialect_name: "bigquery".to_string(),
+        single_quote_strings: true,
+        ..Mode::default()
+    }
+}
+
+fn remove_redundant_parens_mode() -> Mode {
+    Mode {
+        remove_redundant_parens: true,
+        ..Mode::default()
+    }
+}
+
 fn run_golden_test(path: &str, mode: &Mode) {
     let (source, expected) = read_test_data(path);
     let actual = format_string(&source, mode).unwrap_or_else(|e| {
@@ -152,7 +291,6 @@ golden_tests! {
     golden_preformatted_301_multiline_jinjafmt => "tests/data/preformatted/301_multiline_jinjafmt.sql",
     golden_preformatted_302_jinjafmt_multiline_str => "tests/data/preformatted/302_jinjafmt_multiline_str.sql",
     golden_preformatted_303_jinjafmt_more_mutliline_str => "tests/data/preformatted/303_jinjafmt_more_mutliline_str.sql",
-    golden_preformatted_400_create_table => "tests/data/preformatted/400_create_table.sql",
     golden_preformatted_401_create_row_access_policy => "tests/data/preformatted/401_create_row_access_policy.sql",
     golden_preformatted_402_alter_table => "tests/data/preformatted/402_alter_table.sql",
 }
@@ -212,6 +350,18 @@ golden_tests! {
     golden_unformatted_200_base_model => "tests/data/unformatted/200_base_model.sql",
     golden_unformatted_201_basic_snapshot => "tests/data/unformatted/201_basic_snapshot.sql",
     golden_unformatted_202_unpivot_macro => "tests/data/unformatted/202_unpivot_macro.sql",
+    golden_unformatted_214_get_unique_attributes => "tests/data/unformatted/214_get_unique_attributes.sql",
+    golden_unformatted_217_dbt_unit_testing_csv => "tests/data/unformatted/217_dbt_unit_testing_csv.sql",
+    golden_unformatted_218_multiple_c_comments => "tests/data/unformatted/218_multiple_c_comments.sql",
+    golden_unformatted_219_any_all_agg => "tests/data/unformatted/219_any_all_agg.sql",
+    golden_unformatted_221_dbt_config_dollar_quoted => "tests/data/unformatted/221_dbt_config_dollar_quoted.sql",
+    golden_unformatted_222_colorado_claims_extract => "tests/data/unformatted/222_colorado_claims_extract.sql",
+    golden_unformatted_222_jinja_unbalanced_brackets => "tests/data/unformatted/222_jinja_unbalanced_brackets.sql",
+}
+
+// BigQuery models with `#` comments
+golden_tests! {
+    mode: bigquery_mode,
     golden_unformatted_203_gitlab_email_domain_type => "tests/data/unformatted/203_gitlab_email_domain_type.sql",
     golden_unformatted_204_gitlab_tag_validation => "tests/data/unformatted/204_gitlab_tag_validation.sql",
     golden_unformatted_205_rittman_hubspot_deals => "tests/data/unformatted/205_rittman_hubspot_deals.sql",
@@ -223,15 +373,8 @@ golden_tests! {
     golden_unformatted_211_http_2019_cdn_17_20 => "tests/data/unformatted/211_http_2019_cdn_17_20.sql",
     golden_unformatted_212_http_2019_cms_14_02 => "tests/data/unformatted/212_http_2019_cms_14_02.sql",
     golden_unformatted_213_gitlab_fct_sales_funnel_target => "tests/data/unformatted/213_gitlab_fct_sales_funnel_target.sql",
-    golden_unformatted_214_get_unique_attributes => "tests/data/unformatted/214_get_unique_attributes.sql",
     golden_unformatted_215_gitlab_get_backup_table_command => "tests/data/unformatted/215_gitlab_get_backup_table_command.sql",
     golden_unformatted_216_gitlab_zuora_revenue_revenue_contract_line_source => "tests/data/unformatted/216_gitlab_zuora_revenue_revenue_contract_line_source.sql",
-    golden_unformatted_217_dbt_unit_testing_csv => "tests/data/unformatted/217_dbt_unit_testing_csv.sql",
-    golden_unformatted_218_multiple_c_comments => "tests/data/unformatted/218_multiple_c_comments.sql",
-    golden_unformatted_219_any_all_agg => "tests/data/unformatted/219_any_all_agg.sql",
-    golden_unformatted_221_dbt_config_dollar_quoted => "tests/data/unformatted/221_dbt_config_dollar_quoted.sql",
-    golden_unformatted_222_colorado_claims_extract => "tests/data/unformatted/222_colorado_claims_extract.sql",
-    golden_unformatted_222_jinja_unbalanced_brackets => "tests/data/unformatted/222_jinja_unbalanced_brackets.sql",
 }
 
 // ClickHouse dialect tests
@@ -254,8 +397,12 @@ golden_tests! {
 // =============================================================================
 
 golden_tests! {
-    mode: default_mode,
+    mode: bigquery_mode,
     golden_unformatted_400_create_fn_and_select => "tests/data/unformatted/400_create_fn_and_select.sql",
+}
+
+golden_tests! {
+    mode: default_mode,
     golden_unformatted_401_explain_select => "tests/data/unformatted/401_explain_select.sql",
     golden_unformatted_402_delete_from_using => "tests/data/unformatted/402_delete_from_using.sql",
     golden_unformatted_403_grant_revoke => "tests/data/unformatted/403_grant_revoke.sql",
@@ -268,6 +415,171 @@ golden_tests! {
     golden_unformatted_410_create_warehouse => "tests/data/unformatted/410_create_warehouse.sql",
     golden_unformatted_411_create_clone => "tests/data/unformatted/411_create_clone.sql",
     golden_unformatted_412_pragma => "tests/data/unformatted/412_pragma.sql",
+    golden_unformatted_413_insert => "tests/data/unformatted/413_insert.sql",
+    golden_unformatted_414_update => "tests/data/unformatted/414_update.sql",
+    golden_unformatted_415_merge => "tests/data/unformatted/415_merge.sql",
+    golden_unformatted_416_create_table => "tests/data/unformatted/416_create_table.sql",
+    golden_unformatted_417_sql_function_bodies => "tests/data/unformatted/417_sql_function_bodies.sql",
+}
+
+// =============================================================================
+// ClickHouse dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: clickhouse_mode,
+    golden_clickhouse_001_case_sensitive_names => "tests/data/clickhouse/001_case_sensitive_names.sql",
+}
+
+// =============================================================================
+// Snowflake dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: snowflake_mode,
+    golden_snowflake_001_semi_structured => "tests/data/snowflake/001_semi_structured.sql",
+    golden_snowflake_002_stages => "tests/data/snowflake/002_stages.sql",
+    golden_snowflake_003_stage_objects => "tests/data/snowflake/003_stage_objects.sql",
+    golden_snowflake_004_flow_operator => "tests/data/snowflake/004_flow_operator.sql",
+    golden_snowflake_005_match_recognize => "tests/data/snowflake/005_match_recognize.sql",
+    golden_snowflake_006_scripting_blocks => "tests/data/snowflake/006_scripting_blocks.sql",
+}
+
+// =============================================================================
+// PostgreSQL dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: postgres_mode,
+    golden_postgres_001_operators => "tests/data/postgres/001_operators.sql",
+    golden_postgres_002_strings => "tests/data/postgres/002_strings.sql",
+    golden_postgres_003_casts => "tests/data/postgres/003_casts.sql",
+}
+
+golden_tests! {
+    mode: postgres_case_sensitive_mode,
+    golden_postgres_004_case_sensitive_names => "tests/data/postgres/004_case_sensitive_names.sql",
+}
+
+// =============================================================================
+// BigQuery dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: bigquery_mode,
+    golden_bigquery_001_backtick_names => "tests/data/bigquery/001_backtick_names.sql",
+    golden_bigquery_002_literals => "tests/data/bigquery/002_literals.sql",
+    golden_bigquery_003_star_modifiers => "tests/data/bigquery/003_star_modifiers.sql",
+    golden_bigquery_004_compound_types => "tests/data/bigquery/004_compound_types.sql",
+    golden_bigquery_005_procedural_blocks => "tests/data/bigquery/005_procedural_blocks.sql",
+}
+
+// =============================================================================
+// Databricks dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: databricks_mode,
+    golden_databricks_001_create_table_delta => "tests/data/databricks/001_create_table_delta.sql",
+    golden_databricks_002_optimize_merge => "tests/data/databricks/002_optimize_merge.sql",
+    golden_databricks_003_lambdas_widgets => "tests/data/databricks/003_lambdas_widgets.sql",
+}
+
+// =============================================================================
+// Trino dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: trino_mode,
+    golden_trino_001_types => "tests/data/trino/001_types.sql",
+    golden_trino_002_unnest_lambdas => "tests/data/trino/002_unnest_lambdas.sql",
+    golden_trino_003_time_travel_sampling => "tests/data/trino/003_time_travel_sampling.sql",
+}
+
+// =============================================================================
+// T-SQL dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: tsql_mode,
+    golden_tsql_001_bracket_identifiers => "tests/data/tsql/001_bracket_identifiers.sql",
+    golden_tsql_002_batches => "tests/data/tsql/002_batches.sql",
+    golden_tsql_003_control_flow => "tests/data/tsql/003_control_flow.sql",
+}
+
+// =============================================================================
+// MySQL dialect golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: mysql_mode,
+    golden_mysql_001_backticks_comments => "tests/data/mysql/001_backticks_comments.sql",
+    golden_mysql_002_upserts => "tests/data/mysql/002_upserts.sql",
+    golden_mysql_003_delimiter => "tests/data/mysql/003_delimiter.sql",
+}
+
+// =============================================================================
+// Formatting option golden tests
+// =============================================================================
+
+golden_tests! {
+    mode: keyword_case_upper_mode,
+    golden_options_001_keyword_case_upper => "tests/data/options/001_keyword_case_upper.sql",
+}
+
+golden_tests! {
+    mode: keyword_case_preserve_mode,
+    golden_options_002_keyword_case_preserve => "tests/data/options/002_keyword_case_preserve.sql",
+}
+
+golden_tests! {
+    mode: function_case_upper_mode,
+    golden_options_003_function_case_upper => "tests/data/options/003_function_case_upper.sql",
+}
+
+golden_tests! {
+    mode: comma_style_trailing_mode,
+    golden_options_004_comma_style_trailing => "tests/data/options/004_comma_style_trailing.sql",
+}
+
+golden_tests! {
+    mode: default_mode,
+    golden_options_005_comma_style_leading => "tests/data/options/005_comma_style_leading.sql",
+}
+
+golden_tests! {
+    mode: indent_width_2_mode,
+    golden_options_006_indent_width_2 => "tests/data/options/006_indent_width_2.sql",
+}
+
+golden_tests! {
+    mode: indent_style_tabs_mode,
+    golden_options_007_indent_style_tabs => "tests/data/options/007_indent_style_tabs.sql",
+}
+
+golden_tests! {
+    mode: align_aliases_mode,
+    golden_options_008_align_aliases => "tests/data/options/008_align_aliases.sql",
+}
+
+golden_tests! {
+    mode: explicit_aliases_mode,
+    golden_options_009_explicit_aliases => "tests/data/options/009_explicit_aliases.sql",
+}
+
+golden_tests! {
+    mode: normalize_operators_mode,
+    golden_options_010_normalize_operators => "tests/data/options/010_normalize_operators.sql",
+}
+
+golden_tests! {
+    mode: single_quote_strings_mode,
+    golden_options_011_single_quote_strings => "tests/data/options/011_single_quote_strings.sql",
+}
+
+golden_tests! {
+    mode: remove_redundant_parens_mode,
+    golden_options_012_remove_redundant_parens => "tests/data/options/012_remove_redundant_parens.sql",
 }
 
 // =============================================================================
@@ -281,7 +593,7 @@ golden_tests! {
 }
 
 // =============================================================================
-// Error golden tests (4 files) — these should produce parse errors
+// Error golden tests (5 files) — these should produce parse errors
 // =============================================================================
 
 golden_error_tests! {
@@ -289,4 +601,5 @@ golden_error_tests! {
     golden_error_910_unopened_multiline => "tests/data/errors/910_unopened_multiline.sql",
     golden_error_911_unopened_bracket => "tests/data/errors/911_unopened_bracket.sql",
     golden_error_920_unterminated_multiline => "tests/data/errors/920_unterminated_multiline.sql",
+    golden_error_930_hash_comment => "tests/data/errors/930_hash_comment.sql",
 }